@@ -0,0 +1,90 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use Chess_::engine::board::Board;
+use Chess_::engine::minmax::{choose_best_move, SearchOptions, Searcher, DEFAULT_TT_ENTRIES};
+use Chess_::engine::piece::Piece;
+use Chess_::engine::types::{Color, PieceType};
+
+/// 固定的 8x8 殘局（含禁區），讓每次量測的搜尋樹相同
+fn bench_board() -> Board {
+    let mut board = Board::new(8, 8);
+    for pos in [(2, 2), (5, 3), (3, 5)] {
+        board.set_blocked(pos);
+    }
+    let pieces = [
+        (PieceType::King, Color::White, (6, 7)),
+        (PieceType::Rook, Color::White, (0, 7)),
+        (PieceType::Knight, Color::White, (4, 4)),
+        (PieceType::Pawn, Color::White, (1, 5)),
+        (PieceType::Pawn, Color::White, (6, 6)),
+        (PieceType::King, Color::Black, (1, 0)),
+        (PieceType::Queen, Color::Black, (5, 1)),
+        (PieceType::Bishop, Color::Black, (2, 1)),
+        (PieceType::Pawn, Color::Black, (0, 1)),
+        (PieceType::Pawn, Color::Black, (6, 2)),
+    ];
+    for (kind, color, pos) in pieces {
+        board.add_piece(Piece { id: 0, kind, color, pos });
+    }
+    board
+}
+
+fn search_depth_4(c: &mut Criterion) {
+    let board = bench_board();
+    let mut group = c.benchmark_group("choose_best_move");
+    group.sample_size(10);
+    group.bench_function("depth_4", |b| {
+        b.iter(|| choose_best_move(&board, Color::White, 4))
+    });
+    group.finish();
+}
+
+/// 查詢每一格的棋子：依格子索引查表，對照改為索引前逐一掃描所有棋子的做法
+fn piece_lookup(c: &mut Criterion) {
+    let board = bench_board();
+    let squares: Vec<(usize, usize)> = (0..board.height).flat_map(|y| (0..board.width).map(move |x| (x, y))).collect();
+    let mut group = c.benchmark_group("piece_lookup");
+    group.bench_function("indexed", |b| {
+        b.iter(|| squares.iter().filter(|&&pos| board.get_piece_at(black_box(pos)).is_some()).count())
+    });
+    group.bench_function("scan", |b| {
+        b.iter(|| squares.iter().filter(|&&pos| board.pieces.values().any(|p| p.pos == black_box(pos))).count())
+    });
+    group.finish();
+}
+
+/// 深度 5 時分別只開啟一種剪枝，比較各自的效果
+fn search_pruning(c: &mut Criterion) {
+    let board = bench_board();
+    let none = SearchOptions {
+        null_move: false,
+        late_move_reductions: false,
+        pvs: false,
+        aspiration: false,
+        ..Default::default()
+    };
+    let configs = [
+        ("none", none),
+        ("null_move", SearchOptions { null_move: true, ..none }),
+        ("lmr", SearchOptions { late_move_reductions: true, ..none }),
+        ("pvs", SearchOptions { pvs: true, ..none }),
+        ("aspiration", SearchOptions { aspiration: true, ..none }),
+        ("all", SearchOptions::default()),
+    ];
+
+    let mut group = c.benchmark_group("pruning_depth_5");
+    group.sample_size(10);
+    for (name, options) in configs {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut searcher = Searcher::new(DEFAULT_TT_ENTRIES);
+                searcher.options = options;
+                searcher.choose_best_move(&board, Color::White, 5)
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, search_depth_4, piece_lookup, search_pruning);
+criterion_main!(benches);
@@ -1,21 +1,49 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 // use std::borrow::BorrowMut;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use slint::{ModelRc, VecModel, SharedString};
 
 use Chess_::engine;
 use engine::board::Board;
-use engine::types::Position;
-use engine::evaluator::evaluate_board;
-use engine::generator::generate_random_board;
-use engine::game::{GameState, TurnState, check_game_end};
-use engine::movement::get_legal_moves;
+use engine::types::{Color, PieceType, Position};
+use engine::evaluator::DefaultEvaluator;
+use engine::eval_params::EvalParams;
+use engine::generator::{generate_random_board, generate_standard_board};
+use engine::game::{GameState, Player, TurnState, check_game_end};
+use engine::minmax::{SearchLimits, SearchResult, Searcher, StopToken};
+use engine::movement::{get_legal_moves, is_promotion, PROMOTION_PIECES};
+use engine::moves::square_name;
+use engine::skill::{skill_search, SkillLevel};
 
 slint::include_modules!();
 
+/// 電腦每步的思考時間（毫秒），可按「停止」提早走子
+const AI_THINK_MS: u64 = 2000;
+
+/// 電腦對電腦時，每步之間預設的間隔（毫秒）
+const AUTOPLAY_DELAY_MS: u64 = 500;
+
+/// 背景執行緒中的電腦思考：取消旗標，以及搜尋完成後寫入的結果
+struct AiJob {
+    stop: StopToken,
+    result: Arc<Mutex<Option<SearchResult>>>,
+}
+
+impl AiJob {
+    /// 停止思考並捨棄結果（開新局時使用）
+    fn cancel(self) {
+        self.stop.stop();
+    }
+}
+
 fn convert_board_to_gui(board: &Board) -> Vec<SharedString> {
     let mut gui_board = vec![SharedString::from("."); board.width * board.height];
     for y in 0..board.height {
@@ -46,40 +74,303 @@ fn index_to_pos(index: usize, width: usize) -> (usize, usize) {
     (index % width, index / width)
 }
 
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "白方",
+        Color::Black => "黑方",
+    }
+}
+
+/// 電腦走子後的局勢評分：找到將死時顯示哪一方幾步內將死，否則顯示靜態評分
+/// `mover` 為剛走子的電腦，分數以它為正；步數扣掉剛走的這一步
+fn score_label(game: &GameState, result: &SearchResult, mover: Color) -> String {
+    match result.mate_in() {
+        Some(1) => format!("{}將死", color_name(mover)),
+        Some(moves) if moves > 0 => format!("{} {} 步內將死", color_name(mover), moves - 1),
+        Some(moves) if moves < 0 => format!("{} {} 步內將死", color_name(mover.opposite()), -moves),
+        _ => format!("{}", game.evaluate()),
+    }
+}
+
+/// 下拉選單的選項：0 為玩家，其餘依序為各棋力等級的電腦
+fn player_names() -> Vec<SharedString> {
+    std::iter::once(SharedString::from("玩家"))
+        .chain(SkillLevel::ALL.iter().map(|level| SharedString::from(format!("電腦（{}）", level.name()))))
+        .collect()
+}
+
+fn player_from_index(index: i32) -> Player {
+    match SkillLevel::ALL.get((index as usize).wrapping_sub(1)) {
+        Some(&level) => Player::Engine(level),
+        None => Player::Human,
+    }
+}
+
+fn player_index(player: Player) -> i32 {
+    match player {
+        Player::Human => 0,
+        Player::Engine(level) => SkillLevel::ALL.iter().position(|&l| l == level).map_or(0, |i| i as i32 + 1),
+    }
+}
+
+/// 側邊欄的評分明細：先列各項總和，再列每個棋子
+fn update_eval_panel(app: &AppWindow, game: &GameState) {
+    let breakdown = game.breakdown();
+    let terms = breakdown.terms;
+    let mut lines = vec![
+        format!("物料 {:+}", terms.material),
+        format!("位置 {:+}", terms.positional),
+        format!("王的安全 {:+}", terms.king_safety),
+        format!("總分 {:+}", breakdown.total),
+        String::new(),
+    ];
+    for piece in &breakdown.pieces {
+        lines.push(format!(
+            "{:?} {:?} {}：{} / {:+} / {:+} = {:+}",
+            piece.color,
+            piece.kind,
+            square_name(piece.pos, game.board.height),
+            piece.material,
+            piece.positional,
+            piece.king_safety,
+            piece.total
+        ));
+    }
+    let lines: Vec<SharedString> = lines.into_iter().map(SharedString::from).collect();
+    app.set_eval_lines(ModelRc::new(Rc::new(VecModel::from(lines))));
+}
+
+/// 新對局開始時重設整個畫面
+fn reset_view(app: &AppWindow, game: &GameState) {
+    let gui_board = convert_board_to_gui(&game.board);
+    app.set_gameover(false);
+    app.set_promotion_pending(false);
+    app.set_thinking(false);
+    app.set_ai_text(SharedString::new());
+    app.set_board(ModelRc::new(Rc::new(VecModel::from(gui_board))));
+    app.set_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
+    app.set_selected_index(-1);
+    app.set_turn_text(SharedString::from(format!("{:?}", game.turn)));
+    app.set_score_text(SharedString::from(format!("{}", game.evaluate())));
+    update_eval_panel(app, game);
+    app.set_from_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
+    app.set_to_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
+}
+
+/// 執行玩家走子並更新畫面，接著輪到電腦時讓它在背景開始思考
+/// 回傳玩家走子是否成功
+fn play_human_move(
+    app: &AppWindow,
+    game: &mut GameState,
+    ai_job: &RefCell<Option<AiJob>>,
+    from: Position,
+    to: Position,
+    promotion: Option<PieceType>,
+) -> bool {
+    if let Err(e) = game.try_move(from, to, promotion) {
+        println!("非法移動：{}", e);
+        // 可選：清除選取或保留原選擇
+        return false;
+    }
+
+    let gui_board = convert_board_to_gui(&game.board);
+    app.set_turn_text(SharedString::from(format!("{:?}", game.turn)));
+    app.set_score_text(SharedString::from(format!("{}", game.evaluate())));
+    update_eval_panel(app, game);
+    app.set_board(ModelRc::new(Rc::new(VecModel::from(gui_board))));
+    app.set_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
+    app.set_from_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
+    app.set_to_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
+    app.set_selected_index(-1);
+
+    if let Some(result) = check_game_end(&game.board, game.turn.as_color()) {
+        println!("對局結束：{}", result);
+        app.set_turn_text(SharedString::from("game over"));
+        app.set_gameover(true);
+        return true;
+    }
+
+    // ✅ 輪到電腦就在背景思考，畫面不會卡住
+    start_engine_if_needed(app, game, ai_job, Duration::ZERO);
+    true
+}
+
+/// 對局未結束、輪到電腦且沒有正在進行的思考時，開始背景思考
+fn start_engine_if_needed(app: &AppWindow, game: &mut GameState, ai_job: &RefCell<Option<AiJob>>, delay: Duration) {
+    let Some(level) = game.engine_to_move() else { return };
+    if app.get_gameover() || ai_job.borrow().is_some() {
+        return;
+    }
+    *ai_job.borrow_mut() = Some(start_ai_thinking(app, game, level, delay));
+}
+
+/// 在背景執行緒搜尋輪到的一方的走法：先等待 `delay`（電腦對電腦時放慢節奏），
+/// 每完成一輪迭代就把目前的主要變例送回介面顯示，
+/// 搜尋結束後寫入結果並觸發 `ai_finished`，由介面執行緒實際走子
+/// 背景執行緒的亂數產生器由對局的亂數產生器衍生，固定對局種子即可重現電腦的選擇
+fn start_ai_thinking(app: &AppWindow, game: &mut GameState, skill: SkillLevel, delay: Duration) -> AiJob {
+    let board = game.board.clone();
+    let mut rng = StdRng::from_rng(&mut game.rng);
+    let color = game.turn.as_color();
+    let mut searcher = Searcher::with_parts(game.searcher.tt.clone(), game.evaluator.clone());
+    let stop = searcher.stop_token();
+    let result = Arc::new(Mutex::new(None));
+
+    app.set_thinking(true);
+    app.set_ai_text(SharedString::from("思考中…"));
+
+    let progress_stop = stop.clone();
+    let slot = result.clone();
+    let app_weak = app.as_weak();
+    thread::spawn(move || {
+        // 分段等待，開新局或按下停止時不必等完整個間隔
+        let wake = Instant::now() + delay;
+        while Instant::now() < wake && !progress_stop.is_stopped() {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let limits = SearchLimits::time(Duration::from_millis(AI_THINK_MS)).with_threads(threads);
+        let height = board.height;
+        let found = skill_search(&mut searcher, &board, color, skill, limits, &mut rng, |info| {
+            // 已被停止（或已開新局）就不再更新，避免蓋掉新的內容
+            if progress_stop.is_stopped() {
+                return;
+            }
+            let text = format!("深度 {}：{}（{}）", info.depth, info.pv_notation(height), info.score_string());
+            let app = app_weak.clone();
+            let _ = slint::invoke_from_event_loop(move || {
+                if let Some(app) = app.upgrade() {
+                    app.set_ai_text(SharedString::from(text));
+                }
+            });
+        });
+        *slot.lock().unwrap() = Some(found);
+        let _ = slint::invoke_from_event_loop(move || {
+            if let Some(app) = app_weak.upgrade() {
+                app.invoke_ai_finished();
+            }
+        });
+    });
+
+    AiJob { stop, result }
+}
+
+/// 電腦思考完成：執行搜尋到的走法並更新畫面
+fn play_ai_move(app: &AppWindow, game: &mut GameState, result: SearchResult) {
+    let Some(mv) = result.best_move else { return };
+    let mover = game.turn.as_color();
+    if let Err(e) = game.try_move(mv.from, mv.to, mv.promotion) {
+        println!("電腦走子失敗：{}", e);
+        return;
+    }
+    app.set_ai_text(SharedString::from(format!(
+        "{}（{}）",
+        result.pv_notation(game.board.height),
+        result.score_string()
+    )));
+
+    let gui_board = convert_board_to_gui(&game.board);
+    let mut highlight_from = vec![false; game.board.width * game.board.height];
+    let mut highlight_to = vec![false; game.board.width * game.board.height];
+    let (fx, fy) = mv.from;
+    highlight_from[fy * game.board.width + fx] = true;
+    let (tx, ty) = mv.to;
+    highlight_to[ty * game.board.width + tx] = true;
+    app.set_board(ModelRc::new(Rc::new(VecModel::from(gui_board))));
+    app.set_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
+    app.set_selected_index(-1);
+    app.set_turn_text(SharedString::from(format!("{:?}", game.turn)));
+    app.set_score_text(SharedString::from(score_label(game, &result, mover)));
+    update_eval_panel(app, game);
+    app.set_from_highlights(ModelRc::new(Rc::new(VecModel::from(highlight_from))));
+    app.set_to_highlights(ModelRc::new(Rc::new(VecModel::from(highlight_to))));
+
+    // ✅ AI 走完 → 再次檢查遊戲是否結束
+    if let Some(result) = check_game_end(&game.board, game.turn.as_color()) {
+        println!("遊戲結束（AI）：{}", result);
+        app.set_turn_text(SharedString::from("game over"));
+        app.set_gameover(true);
+        app.set_from_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
+        app.set_to_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
+    }
+}
+
 fn main() -> Result<(), slint::PlatformError> {
     let app = AppWindow::new()?;
     let app_weak = app.as_weak();
 
+    // ✅ 評估參數：命令列第一個參數為 .toml / .json 參數檔，沒有給或載入失敗時用預設值
+    let params = match std::env::args().nth(1) {
+        Some(path) => EvalParams::load(&path).unwrap_or_else(|e| {
+            println!("{}，改用預設評估參數", e);
+            EvalParams::default()
+        }),
+        None => EvalParams::default(),
+    };
+
     // ✅ 建立共享狀態
-    let game_state = Rc::new(RefCell::new(GameState::new(generate_random_board(8, 8, 5, 5, 5))));
+    let mut game = GameState::new(generate_random_board(8, 8, 5, 5, 5));
+    game.evaluator = Arc::new(DefaultEvaluator::new(params));
+    let game_state = Rc::new(RefCell::new(game));
     let selected_index: Rc<RefCell<Option<usize>>> = Rc::new(RefCell::new(None));
+    let pending_promotion: Rc<RefCell<Option<(Position, Position)>>> = Rc::new(RefCell::new(None));
+    let ai_job: Rc<RefCell<Option<AiJob>>> = Rc::new(RefCell::new(None));
+    let autoplay_delay = Rc::new(Cell::new(Duration::from_millis(AUTOPLAY_DELAY_MS)));
 
     // 🔁 註冊生成回呼
     {
         let game_state = game_state.clone();
         let selected_index = selected_index.clone();
+        let pending_promotion = pending_promotion.clone();
+        let ai_job = ai_job.clone();
         app.on_generate({
             let app = app_weak.clone();
             move || {
+                if let Some(job) = ai_job.borrow_mut().take() {
+                    job.cancel();
+                }
                 let mut game = game_state.borrow_mut();
+                let (white, black, evaluator) = (game.white, game.black, game.evaluator.clone());
                 *game = GameState::new(generate_random_board(8, 8, 5, 5, 5));
+                (game.white, game.black, game.evaluator) = (white, black, evaluator);
                 game.board.print_board();
 
-                let score = evaluate_board(&game.board);
+                let score = game.evaluate();
                 println!("[GUI 評分] 分數: {}", score);
 
-                let gui_board = convert_board_to_gui(&game.board);
                 if let Some(app) = app.upgrade() {
-                    app.set_gameover(false);
-                    app.set_board(ModelRc::new(Rc::new(VecModel::from(gui_board))));
-                    app.set_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
-                    app.set_selected_index(-1);
-                    app.set_turn_text(SharedString::from(format!("{:?}", game.turn)));
-                    app.set_score_text(SharedString::from(format!("{}", evaluate_board(&game.board))));
-                    app.set_from_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
-                    app.set_to_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
+                    reset_view(&app, &game);
+                    start_engine_if_needed(&app, &mut game, &ai_job, Duration::ZERO);
                 }
                 *selected_index.borrow_mut() = None;
+                *pending_promotion.borrow_mut() = None;
+            }
+        });
+    }
+
+    // 🔁 註冊標準開局回呼
+    {
+        let game_state = game_state.clone();
+        let selected_index = selected_index.clone();
+        let pending_promotion = pending_promotion.clone();
+        let ai_job = ai_job.clone();
+        app.on_standard({
+            let app = app_weak.clone();
+            move || {
+                if let Some(job) = ai_job.borrow_mut().take() {
+                    job.cancel();
+                }
+                let mut game = game_state.borrow_mut();
+                let (white, black, evaluator) = (game.white, game.black, game.evaluator.clone());
+                *game = GameState::new(generate_standard_board(8, 8));
+                (game.white, game.black, game.evaluator) = (white, black, evaluator);
+                if let Some(app) = app.upgrade() {
+                    reset_view(&app, &game);
+                    start_engine_if_needed(&app, &mut game, &ai_job, Duration::ZERO);
+                }
+                *selected_index.borrow_mut() = None;
+                *pending_promotion.borrow_mut() = None;
             }
         });
     }
@@ -88,10 +379,16 @@ fn main() -> Result<(), slint::PlatformError> {
     {
         let game_state = game_state.clone();
         let selected_index = selected_index.clone();
+        let pending_promotion = pending_promotion.clone();
+        let ai_job = ai_job.clone();
         app.on_piece_clicked({
             let app = app_weak.clone();
             move |index| {
+                // 電腦思考中或輪到電腦時不接受點擊
                 let mut game = game_state.borrow_mut();
+                if ai_job.borrow().is_some() || game.engine_to_move().is_some() {
+                    return;
+                }
                 let mut selected = selected_index.borrow_mut();
                 let (x, y) = index_to_pos(index as usize, game.board.width);
                 let pos = (x, y);
@@ -107,7 +404,7 @@ fn main() -> Result<(), slint::PlatformError> {
                             }
                             if let Some(app) = app.upgrade() {
                                 app.set_highlights(ModelRc::new(Rc::new(VecModel::from(highlights))));
-                                app.set_selected_index(index as i32);
+                                app.set_selected_index(index);
                             }
                             *selected = Some(index as usize);
                         }
@@ -124,83 +421,25 @@ fn main() -> Result<(), slint::PlatformError> {
                         return;
                     }
                     // ✅ 嘗試移動
-                    let from_index = selected.unwrap();
                     let from = index_to_pos(from_index, game.board.width);
                     let to = index_to_pos(index as usize, game.board.width);
 
-                    match game.try_move(from, to) {
-                        Ok(_) => {
-                            let gui_board = convert_board_to_gui(&game.board);
-                            if let Some(app) = app.upgrade() {
-                                app.set_turn_text(SharedString::from(format!("{:?}", game.turn)));
-                                app.set_score_text(SharedString::from(format!("{}", evaluate_board(&game.board))));
-                                app.set_board(ModelRc::new(Rc::new(VecModel::from(gui_board))));
-                                app.set_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
-                                app.set_from_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
-                                app.set_to_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
-                                app.set_selected_index(-1);
-                            }
-                            *selected = None;
-                            if let Some(result) = check_game_end(&game.board) {
-                                println!("對局結束：{}", result);
-                                if let Some(app) = app.upgrade() {
-                                    app.set_turn_text(SharedString::from("game over"));
-                                    app.set_gameover(true);
-                                }
-                                return;
-                            }
-
-                             // ✅ 電腦（黑方）自動行動
-                            if game.turn == TurnState::Black {
-                                if let Some((from, to)) = game.try_ai_move(3) {
-                                    println!("AI move: {:?} -> {:?}", from, to);
-
-                                    let gui_board = convert_board_to_gui(&game.board);
-                                    let mut highlight_from = vec![false; game.board.width * game.board.height];
-                                    let mut highlight_to = vec![false; game.board.width * game.board.height];
-                                    let (fx, fy) = from;
-                                    highlight_from[fy * game.board.width + fx] = true;
-                                    let (tx, ty) = to;
-                                    highlight_to[ty * game.board.width + tx] = true;
-                                    if let Some(app) = app.upgrade() {
-                                        app.set_board(ModelRc::new(Rc::new(VecModel::from(gui_board))));
-                                        app.set_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
-                                        app.set_selected_index(-1);
-                                        app.set_turn_text(SharedString::from(format!("{:?}", game.turn)));
-                                        app.set_score_text(SharedString::from(format!("{}", evaluate_board(&game.board))));
-                                        app.set_from_highlights(ModelRc::new(Rc::new(VecModel::from(highlight_from))));
-                                        app.set_to_highlights(ModelRc::new(Rc::new(VecModel::from(highlight_to))));
-                                        // app.set_turn_text(format!("{:?}", game.turn));
-                                        // app.set_score_text(format!("{}", evaluate_board(&game.board)));
-                                    }
-
-                                    // ✅ AI 走完 → 再次檢查遊戲是否結束
-                                    if let Some(result) = check_game_end(&game.board) {
-                                        println!("遊戲結束（AI）：{}", result);
-
-                                        // let mut game = game_state.borrow_mut();
-                                        // *game = GameState::new(generate_random_board(8, 8, 5, 5, 5));
-                                        // let gui_board = convert_board_to_gui(&game.board);
-                                        if let Some(app) = app.upgrade() {
-                                            // app.set_board(ModelRc::new(Rc::new(VecModel::from(gui_board))));
-                                            // app.set_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
-                                            // app.set_selected_index(-1);
-                                            app.set_turn_text(SharedString::from("game over"));
-                                            app.set_gameover(true);
-                                            app.set_from_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
-                                            app.set_to_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
-                                            // app.set_turn_text(SharedString::from(format!("{:?}", game.turn)));
-                                            // app.set_score_text(SharedString::from(format!("{}", evaluate_board(&game.board))));
-                                            // app.set_turn_text(format!("{:?}", game.turn));
-                                            // app.set_score_text(format!("{}", evaluate_board(&game.board)));
-                                        }
-                                    }
-                                }
-                            }
+                    // ✅ 兵走到底線 → 先跳出升變選單
+                    let needs_promotion = game.board.get_piece_at(from)
+                        .map(|p| is_promotion(&game.board, p, to) && get_legal_moves(&game.board, p).contains(&to))
+                        .unwrap_or(false);
+                    if needs_promotion {
+                        *pending_promotion.borrow_mut() = Some((from, to));
+                        if let Some(app) = app.upgrade() {
+                            app.set_promotion_white(game.turn == TurnState::White);
+                            app.set_promotion_pending(true);
                         }
-                        Err(e) => {
-                            println!("非法移動：{}", e);
-                            // 可選：清除選取或保留原選擇
+                        return;
+                    }
+
+                    if let Some(app) = app.upgrade() {
+                        if play_human_move(&app, &mut game, &ai_job, from, to, None) {
+                            *selected = None;
                         }
                     }
                 }
@@ -208,6 +447,93 @@ fn main() -> Result<(), slint::PlatformError> {
         });
     }
 
+    // 🔁 註冊升變選擇回呼
+    {
+        let game_state = game_state.clone();
+        let selected_index = selected_index.clone();
+        let pending_promotion = pending_promotion.clone();
+        let ai_job = ai_job.clone();
+        app.on_promotion_chosen({
+            let app = app_weak.clone();
+            move |choice| {
+                let Some(app) = app.upgrade() else { return };
+                app.set_promotion_pending(false);
+                let Some((from, to)) = pending_promotion.borrow_mut().take() else { return };
+                let kind = PROMOTION_PIECES[choice as usize];
+                let mut game = game_state.borrow_mut();
+                if play_human_move(&app, &mut game, &ai_job, from, to, Some(kind)) {
+                    *selected_index.borrow_mut() = None;
+                }
+            }
+        });
+    }
+
+    // 🔁 註冊停止思考回呼：要求搜尋立即回傳目前最好的走法
+    {
+        let ai_job = ai_job.clone();
+        app.on_stop_thinking(move || {
+            if let Some(job) = ai_job.borrow().as_ref() {
+                job.stop.stop();
+            }
+        });
+    }
+
+    // 🔁 註冊電腦思考完成回呼（由背景執行緒透過事件迴圈觸發）
+    // 下一方也是電腦時，等待間隔後接著思考（電腦對電腦自動對弈）
+    {
+        let game_state = game_state.clone();
+        let ai_job = ai_job.clone();
+        let autoplay_delay = autoplay_delay.clone();
+        app.on_ai_finished({
+            let app = app_weak.clone();
+            move || {
+                let Some(app) = app.upgrade() else { return };
+                // 已取消的舊工作寫入的是自己的結果，目前的工作仍是空的 → 忽略
+                let Some(result) = ai_job.borrow().as_ref().and_then(|job| job.result.lock().unwrap().take()) else {
+                    return;
+                };
+                *ai_job.borrow_mut() = None;
+                app.set_thinking(false);
+                let mut game = game_state.borrow_mut();
+                play_ai_move(&app, &mut game, result);
+                start_engine_if_needed(&app, &mut game, &ai_job, autoplay_delay.get());
+            }
+        });
+    }
+
+    // 🔁 註冊雙方操控者選擇回呼：輪到的一方改為玩家時停止思考，改為電腦時開始思考
+    {
+        let game_state = game_state.clone();
+        let ai_job = ai_job.clone();
+        app.on_player_changed({
+            let app = app_weak.clone();
+            move |white, index| {
+                let Some(app) = app.upgrade() else { return };
+                let color = if white { Color::White } else { Color::Black };
+                let player = player_from_index(index);
+                let mut game = game_state.borrow_mut();
+                game.set_player(color, player);
+
+                if color == game.turn.as_color() {
+                    if let Some(job) = ai_job.borrow_mut().take() {
+                        job.cancel();
+                        app.set_thinking(false);
+                        app.set_ai_text(SharedString::new());
+                    }
+                    start_engine_if_needed(&app, &mut game, &ai_job, Duration::ZERO);
+                }
+            }
+        });
+    }
+
+    // 🔁 註冊自動對弈間隔回呼
+    {
+        let autoplay_delay = autoplay_delay.clone();
+        app.on_autoplay_delay_changed(move |ms| {
+            autoplay_delay.set(Duration::from_millis(ms.max(0.0) as u64));
+        });
+    }
+
     // ✅ 初始畫面載入
     {
         let game = game_state.borrow();
@@ -216,10 +542,16 @@ fn main() -> Result<(), slint::PlatformError> {
         app.set_highlights(ModelRc::new(Rc::new(VecModel::from(vec![false; 64]))));
         app.set_selected_index(-1);
         app.set_turn_text(SharedString::from(format!("{:?}", game.turn)));
-        app.set_score_text(SharedString::from(format!("{}", evaluate_board(&game.board))));
+        app.set_score_text(SharedString::from(format!("{}", game.evaluate())));
+        update_eval_panel(&app, &game);
         let size = game.board.width * game.board.height;
         app.set_from_highlights(empty_bool_vec(size));
         app.set_to_highlights(empty_bool_vec(size));
+
+        app.set_player_names(ModelRc::new(Rc::new(VecModel::from(player_names()))));
+        app.set_white_player(player_index(game.white));
+        app.set_black_player(player_index(game.black));
+        app.set_autoplay_delay(autoplay_delay.get().as_millis() as f32);
     }
 
     app.run()
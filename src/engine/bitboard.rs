@@ -0,0 +1,295 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not};
+
+use super::board::Board;
+use super::piece::Piece;
+use super::types::{Color, PieceType, Position};
+
+/// 位元棋盤支援的最大寬度與高度
+pub const MAX_SIZE: usize = 16;
+
+/// 256 位元的棋盤集合，第 `y * 16 + x` 位元代表格子 (x, y)
+/// 每一列固定佔 16 位元，所以寬度小於 16 時右側多出的位元永遠不在 `valid` 內
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Bitboard(pub [u64; 4]);
+
+impl Bitboard {
+    pub const EMPTY: Bitboard = Bitboard([0; 4]);
+
+    /// 只包含單一格子的集合
+    pub fn square(pos: Position) -> Self {
+        let mut bb = Bitboard::EMPTY;
+        bb.set(pos);
+        bb
+    }
+
+    /// 每一列都套用相同 16 位元樣式的集合（用來做檔案遮罩）
+    fn every_row(pattern: u16) -> Self {
+        let row = pattern as u64;
+        let limb = row | (row << 16) | (row << 32) | (row << 48);
+        Bitboard([limb; 4])
+    }
+
+    pub fn set(&mut self, pos: Position) {
+        let idx = index(pos);
+        self.0[idx / 64] |= 1 << (idx % 64);
+    }
+
+    pub fn clear(&mut self, pos: Position) {
+        let idx = index(pos);
+        self.0[idx / 64] &= !(1 << (idx % 64));
+    }
+
+    pub fn contains(&self, pos: Position) -> bool {
+        let idx = index(pos);
+        self.0[idx / 64] & (1 << (idx % 64)) != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == [0; 4]
+    }
+
+    pub fn count(&self) -> u32 {
+        self.0.iter().map(|limb| limb.count_ones()).sum()
+    }
+
+    /// 往高位元平移 `n`（0 < n < 64）
+    fn shl(self, n: u32) -> Self {
+        let l = self.0;
+        Bitboard([
+            l[0] << n,
+            (l[1] << n) | (l[0] >> (64 - n)),
+            (l[2] << n) | (l[1] >> (64 - n)),
+            (l[3] << n) | (l[2] >> (64 - n)),
+        ])
+    }
+
+    /// 往低位元平移 `n`（0 < n < 64）
+    fn shr(self, n: u32) -> Self {
+        let l = self.0;
+        Bitboard([
+            (l[0] >> n) | (l[1] << (64 - n)),
+            (l[1] >> n) | (l[2] << (64 - n)),
+            (l[2] >> n) | (l[3] << (64 - n)),
+            l[3] >> n,
+        ])
+    }
+
+    /// 整體往 (dx, dy) 方向移動，越過左右邊界的位元會被丟掉（|dx| <= 2）
+    pub fn shift(self, dx: isize, dy: isize) -> Self {
+        let n = dy * MAX_SIZE as isize + dx;
+        let moved = match n {
+            0 => self,
+            n if n > 0 => self.shl(n as u32),
+            n => self.shr((-n) as u32),
+        };
+        match dx {
+            // 往右移時，從上一列最右側繞回來的位元會落在最左側的 dx 行
+            dx if dx > 0 => moved & !Bitboard::every_row((1u16 << dx) - 1),
+            dx if dx < 0 => moved & !Bitboard::every_row(!(u16::MAX >> -dx)),
+            _ => moved,
+        }
+    }
+
+    /// 依位元順序（先 y 後 x）列出所有格子
+    pub fn positions(self) -> Vec<Position> {
+        let mut out = Vec::with_capacity(self.count() as usize);
+        for (i, mut limb) in self.0.into_iter().enumerate() {
+            while limb != 0 {
+                let idx = i * 64 + limb.trailing_zeros() as usize;
+                out.push((idx % MAX_SIZE, idx / MAX_SIZE));
+                limb &= limb - 1;
+            }
+        }
+        out
+    }
+}
+
+fn index(pos: Position) -> usize {
+    pos.1 * MAX_SIZE + pos.0
+}
+
+impl BitAnd for Bitboard {
+    type Output = Bitboard;
+    fn bitand(self, rhs: Self) -> Self {
+        Bitboard([self.0[0] & rhs.0[0], self.0[1] & rhs.0[1], self.0[2] & rhs.0[2], self.0[3] & rhs.0[3]])
+    }
+}
+
+impl BitOr for Bitboard {
+    type Output = Bitboard;
+    fn bitor(self, rhs: Self) -> Self {
+        Bitboard([self.0[0] | rhs.0[0], self.0[1] | rhs.0[1], self.0[2] | rhs.0[2], self.0[3] | rhs.0[3]])
+    }
+}
+
+impl Not for Bitboard {
+    type Output = Bitboard;
+    fn not(self) -> Self {
+        Bitboard([!self.0[0], !self.0[1], !self.0[2], !self.0[3]])
+    }
+}
+
+impl BitAndAssign for Bitboard {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = *self & rhs;
+    }
+}
+
+impl BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+/// 棋盤的位元集合：可用格子、禁區與雙方佔據的格子
+/// 由 `Board` 在放置、移動、移除棋子與設定禁區時同步更新
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardBits {
+    pub valid: Bitboard,
+    pub blocked: Bitboard,
+    pub white: Bitboard,
+    pub black: Bitboard,
+}
+
+impl BoardBits {
+    /// 超過 16x16 的棋盤無法以位元棋盤表示，回傳 None
+    pub fn new(width: usize, height: usize) -> Option<Self> {
+        if width > MAX_SIZE || height > MAX_SIZE {
+            return None;
+        }
+        let mut valid = Bitboard::EMPTY;
+        for x in 0..width {
+            for y in 0..height {
+                valid.set((x, y));
+            }
+        }
+        Some(BoardBits {
+            valid,
+            blocked: Bitboard::EMPTY,
+            white: Bitboard::EMPTY,
+            black: Bitboard::EMPTY,
+        })
+    }
+
+    pub fn occupied(&self) -> Bitboard {
+        self.white | self.black
+    }
+
+    pub fn side(&self, color: Color) -> Bitboard {
+        match color {
+            Color::White => self.white,
+            Color::Black => self.black,
+        }
+    }
+
+    pub fn side_mut(&mut self, color: Color) -> &mut Bitboard {
+        match color {
+            Color::White => &mut self.white,
+            Color::Black => &mut self.black,
+        }
+    }
+
+    /// 可以進入的格子（在棋盤內且不是禁區）
+    pub fn passable(&self) -> Bitboard {
+        self.valid & !self.blocked
+    }
+}
+
+const KNIGHT_DELTAS: [(isize, isize); 8] = [
+    (2, 1), (1, 2), (-1, 2), (-2, 1),
+    (-2, -1), (-1, -2), (1, -2), (2, -1),
+];
+
+const KING_DELTAS: [(isize, isize); 8] = [
+    (-1, -1), (-1, 0), (-1, 1),
+    (0, -1),           (0, 1),
+    (1, -1),  (1, 0),  (1, 1),
+];
+
+const ROOK_DIRECTIONS: [(isize, isize); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+const BISHOP_DIRECTIONS: [(isize, isize); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+/// 以位元棋盤計算棋子的偽合法目標格，結果與 `movement::array_pseudo_legal_moves` 完全相同（包含順序），
+/// 因此開啟 feature 後搜尋走訪同分走法的順序不變
+pub fn pseudo_legal_moves(board: &Board, bits: &BoardBits, piece: &Piece) -> Vec<Position> {
+    let from = Bitboard::square(piece.pos);
+    let allowed = bits.passable() & !bits.side(piece.color);
+    match piece.kind {
+        PieceType::Knight => step_targets(from, &KNIGHT_DELTAS, allowed),
+        PieceType::King => step_targets(from, &KING_DELTAS, allowed),
+        PieceType::Rook => slide_targets(from, &ROOK_DIRECTIONS, piece.color, bits),
+        PieceType::Bishop => slide_targets(from, &BISHOP_DIRECTIONS, piece.color, bits),
+        PieceType::Queen => {
+            let mut targets = slide_targets(from, &ROOK_DIRECTIONS, piece.color, bits);
+            targets.extend(slide_targets(from, &BISHOP_DIRECTIONS, piece.color, bits));
+            targets
+        }
+        PieceType::Pawn => pawn_targets(board, bits, piece, from),
+        PieceType::Custom(_) => vec![],
+    }
+}
+
+/// 騎士與國王：依位移順序各走一步，落在 `allowed` 內的格子
+fn step_targets(from: Bitboard, deltas: &[(isize, isize)], allowed: Bitboard) -> Vec<Position> {
+    deltas.iter()
+        .flat_map(|&(dx, dy)| (from.shift(dx, dy) & allowed).positions())
+        .collect()
+}
+
+/// 車、象、后：依方向順序由近到遠延伸，遇到禁區或己方棋子前停下，遇到敵方棋子則包含該格後停下
+fn slide_targets(from: Bitboard, directions: &[(isize, isize)], color: Color, bits: &BoardBits) -> Vec<Position> {
+    let passable = bits.passable();
+    let own = bits.side(color);
+    let occupied = bits.occupied();
+    let mut targets = Vec::new();
+    for &(dx, dy) in directions {
+        let mut ray = from;
+        loop {
+            ray = ray.shift(dx, dy) & passable;
+            if ray.is_empty() || !(ray & own).is_empty() {
+                break;
+            }
+            targets.extend(ray.positions());
+            if !(ray & occupied).is_empty() {
+                break;
+            }
+        }
+    }
+    targets
+}
+
+/// 兵：先前進（起始列可再前進一格），再依左斜、右斜吃子（含吃過路兵）
+fn pawn_targets(board: &Board, bits: &BoardBits, piece: &Piece, from: Bitboard) -> Vec<Position> {
+    let direction: isize = match piece.color {
+        Color::White => -1,
+        Color::Black => 1,
+    };
+    let empty = bits.passable() & !bits.occupied();
+    let mut targets = Vec::new();
+
+    let forward = from.shift(0, direction) & empty;
+    targets.extend(forward.positions());
+    let start_rank = match piece.color {
+        Color::White => board.height.wrapping_sub(2),
+        Color::Black => 1,
+    };
+    if board.pawn_double_step && piece.pos.1 == start_rank && !forward.is_empty() {
+        targets.extend((forward.shift(0, direction) & empty).positions());
+    }
+
+    let enemy = bits.side(piece.color.opposite());
+    for dx in [-1, 1] {
+        let attack = from.shift(dx, direction) & bits.valid;
+        if !(attack & enemy).is_empty() {
+            targets.extend(attack.positions());
+        } else if let Some(ep) = board.en_passant {
+            let victim_is_pawn = board.get_piece_at((ep.0, piece.pos.1))
+                .map(|p| p.kind == PieceType::Pawn && p.color != piece.color)
+                .unwrap_or(false);
+            if attack.contains(ep) && victim_is_pawn && !bits.occupied().contains(ep) {
+                targets.push(ep);
+            }
+        }
+    }
+    targets
+}
@@ -1,7 +1,12 @@
 use std::collections::HashMap;
 
+#[cfg(feature = "bitboard")]
+use super::bitboard::BoardBits;
+use super::castling::{castling_rook_move, CastlingRights};
+use super::moves::{Move, MoveKind, UndoInfo};
 use super::piece::Piece;
-use super::types::{Position, Tile, PieceType::*};
+use super::types::{PieceType, Position, Tile, PieceType::*};
+use super::zobrist;
 
 #[derive(Clone)]
 pub struct Board {
@@ -10,25 +15,51 @@ pub struct Board {
     pub tiles: Vec<Vec<Tile>>, // 棋盤格子狀態：Empty / Blocked
     pub pieces: HashMap<u32, Piece>, // 棋子編號 → 棋子
     pub next_id: u32, // 棋子 ID 自動遞增用
+    pub pawn_double_step: bool, // 兵在起始列是否可前進兩格
+    pub en_passant: Option<Position>, // 可吃過路兵的目標格（上一步兵前進兩格時跨過的格子）
+    pub castling: CastlingRights, // 王車易位權利
+    // 格子 → 棋子 ID 的索引（與 tiles 同為 [x][y]），由 add/move/remove 系列方法維持同步，
+    // 讓 get_piece_at 不必掃描整個 pieces；因此請勿直接修改 pieces
+    squares: Vec<Vec<Option<u32>>>,
+    // 位元棋盤（僅在 16x16 以內的棋盤上存在），同樣由上述方法維持同步
+    #[cfg(feature = "bitboard")]
+    bits: Option<BoardBits>,
+    // 增量維護的 Zobrist 雜湊（尺寸、禁區、棋子），過路兵與易位權利在 hash() 時才加入
+    zobrist: u64,
 }
 
 impl Board {
     /// 建立新的棋盤，預設所有格子都是空的
     pub fn new(width: usize, height: usize) -> Self {
         let tiles = vec![vec![Tile::Empty; height]; width];
+        let squares = vec![vec![None; height]; width];
         Board {
             width,
             height,
             tiles,
             pieces: HashMap::new(),
             next_id: 1,
+            pawn_double_step: false,
+            en_passant: None,
+            castling: CastlingRights::default(),
+            squares,
+            #[cfg(feature = "bitboard")]
+            bits: BoardBits::new(width, height),
+            zobrist: zobrist::dimensions_key(width, height),
         }
     }
 
     /// 標記禁區（破洞）
     pub fn set_blocked(&mut self, pos: Position) {
         if self.in_bounds(pos) {
+            if self.tiles[pos.0][pos.1] != Tile::Blocked {
+                self.zobrist ^= zobrist::blocked_key(pos);
+            }
             self.tiles[pos.0][pos.1] = Tile::Blocked;
+            #[cfg(feature = "bitboard")]
+            if let Some(bits) = &mut self.bits {
+                bits.blocked.set(pos);
+            }
         }
     }
 
@@ -36,9 +67,16 @@ impl Board {
     pub fn clear_tiles(&mut self) {
         for x in 0..self.width {
             for y in 0..self.height {
+                if self.tiles[x][y] == Tile::Blocked {
+                    self.zobrist ^= zobrist::blocked_key((x, y));
+                }
                 self.tiles[x][y] = Tile::Empty;
             }
         }
+        #[cfg(feature = "bitboard")]
+        if let Some(bits) = &mut self.bits {
+            bits.blocked = Default::default();
+        }
     }
 
     /// 新增一個棋子到指定位置，並自動分配 ID
@@ -47,19 +85,30 @@ impl Board {
             return u32::MAX; // 表示錯誤或無法放置
         }
         piece.id = self.next_id;
-        self.pieces.insert(self.next_id, piece);
+        self.insert_piece(piece);
         self.next_id += 1;
         self.next_id - 1
     }
 
     /// 移除指定棋子 ID
     pub fn remove_piece(&mut self, id: u32) {
-        self.pieces.remove(&id);
+        if let Some(piece) = self.pieces.remove(&id) {
+            self.clear_square(&piece);
+        }
     }
 
     /// 取得某格子的棋子（如果有）
     pub fn get_piece_at(&self, pos: Position) -> Option<&Piece> {
-        self.pieces.values().find(|p| p.pos == pos)
+        let id = self.piece_id_at(pos)?;
+        self.pieces.get(&id)
+    }
+
+    /// 取得某格子的棋子 ID（如果有）
+    pub fn piece_id_at(&self, pos: Position) -> Option<u32> {
+        if !self.in_bounds(pos) {
+            return None;
+        }
+        self.squares[pos.0][pos.1]
     }
 
     /// 判斷是否在邊界內
@@ -85,7 +134,18 @@ impl Board {
 
     /// 清除所有棋子
     pub fn clear_pieces(&mut self) {
+        for piece in self.pieces.values() {
+            self.zobrist ^= zobrist::piece_key(piece.kind, piece.color, piece.pos);
+        }
         self.pieces.clear();
+        for column in self.squares.iter_mut() {
+            column.fill(None);
+        }
+        #[cfg(feature = "bitboard")]
+        if let Some(bits) = &mut self.bits {
+            bits.white = Default::default();
+            bits.black = Default::default();
+        }
         self.next_id = 1;
     }
 
@@ -111,24 +171,197 @@ impl Board {
     }
     /// 移除某格上的棋子（如果有）
     pub fn remove_piece_at(&mut self, pos: Position) {
-        let id_to_remove = self
-            .pieces
-            .values()
-            .find(|p| p.pos == pos)
-            .map(|p| p.id);
-
-        if let Some(id) = id_to_remove {
-            self.pieces.remove(&id);
+        if let Some(id) = self.piece_id_at(pos) {
+            self.remove_piece(id);
         }
     }
 
     /// 將指定 ID 的棋子移動到新位置（不進行合法性檢查）
     pub fn move_piece(&mut self, id: u32, new_pos: Position) {
+        let (old_pos, moved) = match self.pieces.get_mut(&id) {
+            Some(piece) => (std::mem::replace(&mut piece.pos, new_pos), *piece),
+            None => return,
+        };
+        self.clear_square(&Piece { pos: old_pos, ..moved });
+        self.occupy_square(&moved);
+    }
+
+    /// 放入一個已有 ID 的棋子並更新格子索引
+    fn insert_piece(&mut self, piece: Piece) {
+        self.occupy_square(&piece);
+        self.pieces.insert(piece.id, piece);
+    }
+
+    /// 將格子索引指向該棋子
+    fn occupy_square(&mut self, piece: &Piece) {
+        if !self.in_bounds(piece.pos) {
+            return;
+        }
+        self.squares[piece.pos.0][piece.pos.1] = Some(piece.id);
+        self.zobrist ^= zobrist::piece_key(piece.kind, piece.color, piece.pos);
+        #[cfg(feature = "bitboard")]
+        if let Some(bits) = &mut self.bits {
+            bits.side_mut(piece.color).set(piece.pos);
+            bits.side_mut(piece.color.opposite()).clear(piece.pos);
+        }
+    }
+
+    /// 若格子索引仍指向該棋子則清空
+    fn clear_square(&mut self, piece: &Piece) {
+        let pos = piece.pos;
+        if self.in_bounds(pos) && self.squares[pos.0][pos.1] == Some(piece.id) {
+            self.squares[pos.0][pos.1] = None;
+            self.zobrist ^= zobrist::piece_key(piece.kind, piece.color, pos);
+            #[cfg(feature = "bitboard")]
+            if let Some(bits) = &mut self.bits {
+                bits.white.clear(pos);
+                bits.black.clear(pos);
+            }
+        }
+    }
+
+    /// 改變棋子種類（兵升變用）並更新雜湊
+    fn set_kind(&mut self, id: u32, kind: PieceType) {
         if let Some(piece) = self.pieces.get_mut(&id) {
-            piece.pos = new_pos;
+            let old = std::mem::replace(&mut piece.kind, kind);
+            self.zobrist ^= zobrist::piece_key(old, piece.color, piece.pos)
+                ^ zobrist::piece_key(kind, piece.color, piece.pos);
+        }
+    }
+
+    /// 棋盤的 Zobrist 雜湊值：尺寸、禁區、棋子（棋種、顏色、格子）、過路兵目標格與易位權利
+    /// 不含輪到哪一方，需要時請用 `zobrist::position_hash`
+    pub fn hash(&self) -> u64 {
+        self.zobrist
+            ^ zobrist::en_passant_key(self.en_passant)
+            ^ zobrist::castling_key(&self.castling)
+    }
+
+    /// 從頭重新計算雜湊值（檢查增量更新是否正確用）
+    pub fn compute_hash(&self) -> u64 {
+        let mut hash = zobrist::dimensions_key(self.width, self.height);
+        for x in 0..self.width {
+            for y in 0..self.height {
+                if self.tiles[x][y] == Tile::Blocked {
+                    hash ^= zobrist::blocked_key((x, y));
+                }
+            }
+        }
+        for piece in self.pieces.values() {
+            hash ^= zobrist::piece_key(piece.kind, piece.color, piece.pos);
+        }
+        hash ^ zobrist::en_passant_key(self.en_passant) ^ zobrist::castling_key(&self.castling)
+    }
+
+    /// 位元棋盤（超過 16x16 的棋盤為 None）
+    #[cfg(feature = "bitboard")]
+    pub fn bits(&self) -> Option<&BoardBits> {
+        self.bits.as_ref()
+    }
+
+    /// 執行一步棋並回傳還原資訊：吃子（含吃過路兵）、移動棋子、兵升變與王車易位，
+    /// 同時更新過路兵目標格與易位權利。被吃的棋子由棋盤判斷：`to` 上的棋子，
+    /// 吃過路兵時則是 `to` 旁邊的兵。不進行合法性檢查；起點沒有棋子時不改動棋盤
+    pub fn make_move(&mut self, mv: &Move) -> UndoInfo {
+        let mut undo = UndoInfo {
+            en_passant: self.en_passant,
+            castling: self.castling,
+            captured: None,
+            moved: None,
+        };
+        let (id, kind, color) = match self.get_piece_at(mv.from) {
+            Some(p) => (p.id, p.kind, p.color),
+            None => return undo,
+        };
+        undo.moved = Some(id);
+
+        let victim_pos = match mv.kind {
+            MoveKind::EnPassant => Some((mv.to.0, mv.from.1)),
+            MoveKind::Castle => None,
+            _ => Some(mv.to),
+        };
+        undo.captured = victim_pos.and_then(|pos| self.get_piece_at(pos)).copied();
+        if let Some(captured) = undo.captured {
+            self.remove_piece(captured.id);
+        }
+
+        // 王車易位：把車移到王跨過的格子
+        if mv.kind == MoveKind::Castle {
+            if let Some((rook_from, rook_to)) = castling_rook_move(self, color, mv.from, mv.to) {
+                if let Some(rook_id) = self.get_piece_at(rook_from).map(|p| p.id) {
+                    self.move_piece(rook_id, rook_to);
+                }
+            }
+        }
+
+        self.move_piece(id, mv.to);
+        if let Some(promoted) = mv.promotion {
+            self.set_kind(id, promoted);
+        }
+
+        // 王移動取消該方易位權利；車的原始格子有變動則取消該側權利
+        if kind == King {
+            self.castling.clear_color(color);
         }
+        self.castling.clear_square(mv.from);
+        self.castling.clear_square(mv.to);
+
+        // 兵前進兩格時記錄跨過的格子，否則過路兵機會消失
+        self.en_passant = if mv.kind == MoveKind::DoubleStep {
+            Some((mv.from.0, (mv.from.1 + mv.to.1) / 2))
+        } else {
+            None
+        };
+
+        undo
+    }
+
+    /// 空著：不移動任何棋子，只讓過路兵機會消失（輪到哪一方由搜尋端記錄）
+    pub fn make_null_move(&mut self) -> UndoInfo {
+        let undo = UndoInfo {
+            en_passant: self.en_passant,
+            castling: self.castling,
+            captured: None,
+            moved: None,
+        };
+        self.en_passant = None;
+        undo
+    }
+
+    /// 還原 `make_null_move`
+    pub fn unmake_null_move(&mut self, undo: UndoInfo) {
+        self.en_passant = undo.en_passant;
     }
 
+    /// 還原 `make_move` 執行的一步棋，棋盤回到走子前完全相同的狀態（含棋子 ID）
+    pub fn unmake_move(&mut self, mv: &Move, undo: UndoInfo) {
+        self.en_passant = undo.en_passant;
+        self.castling = undo.castling;
+
+        let Some(id) = undo.moved else {
+            return;
+        };
+        let color = match self.pieces.get(&id) {
+            Some(p) => p.color,
+            None => return,
+        };
+        self.move_piece(id, mv.from);
+        if mv.promotion.is_some() {
+            self.set_kind(id, Pawn);
+        }
+
+        if mv.kind == MoveKind::Castle {
+            if let Some((rook_from, rook_to)) = castling_rook_move(self, color, mv.from, mv.to) {
+                if let Some(rook_id) = self.get_piece_at(rook_to).map(|p| p.id) {
+                    self.move_piece(rook_id, rook_from);
+                }
+            }
+        }
+
+        if let Some(captured) = undo.captured {
+            self.insert_piece(captured);
+        }
+    }
 }
 
 fn piece_symbol(piece: &Piece) -> char {
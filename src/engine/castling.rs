@@ -0,0 +1,105 @@
+use super::board::Board;
+use super::types::{Color, PieceType, Position};
+
+/// 王車易位權利：記錄每一方兩側仍可易位的車的原始位置
+/// 王移動後該方權利全部消失；車移動或被吃後該側權利消失
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CastlingRights {
+    pub white_king_side: Option<Position>,
+    pub white_queen_side: Option<Position>,
+    pub black_king_side: Option<Position>,
+    pub black_queen_side: Option<Position>,
+}
+
+impl CastlingRights {
+    /// 從標準開局擺法偵測易位權利：
+    /// 王在己方底線（白方 y = height - 1、黑方 y = 0），車在同一列的兩個角落，
+    /// 且王與車之間至少隔兩格（王要往車的方向走兩格）
+    pub fn detect(board: &Board) -> Self {
+        let mut rights = CastlingRights::default();
+        if board.width == 0 || board.height == 0 {
+            return rights;
+        }
+        for color in [Color::White, Color::Black] {
+            let y = home_rank(board, color);
+            let king_x = match board.pieces.values()
+                .find(|p| p.kind == PieceType::King && p.color == color && p.pos.1 == y)
+            {
+                Some(king) => king.pos.0,
+                None => continue,
+            };
+            let has_rook = |x: usize| board.get_piece_at((x, y))
+                .map(|p| p.kind == PieceType::Rook && p.color == color)
+                .unwrap_or(false);
+
+            let right = board.width - 1;
+            if right >= king_x + 3 && has_rook(right) {
+                *rights.get_mut(color, true) = Some((right, y));
+            }
+            if king_x >= 3 && has_rook(0) {
+                *rights.get_mut(color, false) = Some((0, y));
+            }
+        }
+        rights
+    }
+
+    /// 取得某一方某一側（`king_side` 為 x 較大的一側）的車位置
+    pub fn get(&self, color: Color, king_side: bool) -> Option<Position> {
+        match (color, king_side) {
+            (Color::White, true) => self.white_king_side,
+            (Color::White, false) => self.white_queen_side,
+            (Color::Black, true) => self.black_king_side,
+            (Color::Black, false) => self.black_queen_side,
+        }
+    }
+
+    fn get_mut(&mut self, color: Color, king_side: bool) -> &mut Option<Position> {
+        match (color, king_side) {
+            (Color::White, true) => &mut self.white_king_side,
+            (Color::White, false) => &mut self.white_queen_side,
+            (Color::Black, true) => &mut self.black_king_side,
+            (Color::Black, false) => &mut self.black_queen_side,
+        }
+    }
+
+    /// 是否還有任何易位權利
+    pub fn any(&self) -> bool {
+        *self != CastlingRights::default()
+    }
+
+    /// 取消某一方所有易位權利（王移動時）
+    pub fn clear_color(&mut self, color: Color) {
+        *self.get_mut(color, true) = None;
+        *self.get_mut(color, false) = None;
+    }
+
+    /// 車的原始格子有棋子離開或被吃時，取消對應權利
+    pub fn clear_square(&mut self, pos: Position) {
+        for right in [
+            &mut self.white_king_side,
+            &mut self.white_queen_side,
+            &mut self.black_king_side,
+            &mut self.black_queen_side,
+        ] {
+            if *right == Some(pos) {
+                *right = None;
+            }
+        }
+    }
+}
+
+/// 某一方的底線（白兵往 y = 0 前進，所以白方底線在 y = height - 1）
+pub fn home_rank(board: &Board, color: Color) -> usize {
+    match color {
+        Color::White => board.height - 1,
+        Color::Black => 0,
+    }
+}
+
+/// 易位時車的起點與終點：王走兩格，車落在王跨過的格子
+pub fn castling_rook_move(board: &Board, color: Color, king_from: Position, king_to: Position) -> Option<(Position, Position)> {
+    let king_side = king_to.0 > king_from.0;
+    let rook_from = board.castling.get(color, king_side)?;
+    let rook_to = ((king_from.0 + king_to.0) / 2, king_from.1);
+    Some((rook_from, rook_to))
+}
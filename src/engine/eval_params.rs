@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::evaluator::piece_value;
+use crate::engine::pst::PieceSquareTables;
+use crate::engine::types::PieceType;
+
+/// 預設評估使用的參數，可從 TOML 或 JSON 檔載入，不必重新編譯就能針對特殊棋盤調整
+/// 檔案中沒寫到的欄位沿用預設值
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvalParams {
+    pub pieces: PieceValues,
+    /// 位置分數表，依棋盤大小重新取樣
+    pub positional: PieceSquareTables,
+    pub king_safety: KingSafetyWeights,
+}
+
+/// 各棋種的物料分數
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PieceValues {
+    pub king: i32,
+    pub queen: i32,
+    pub rook: i32,
+    pub bishop: i32,
+    pub knight: i32,
+    pub pawn: i32,
+    /// 自訂棋種（以編號為鍵）的分數，沒有列出的自訂棋種以編號作為分數
+    pub custom: BTreeMap<u8, i32>,
+}
+
+/// 王的安全性：王在邊線上加 `edge_bonus`，不在邊線上扣 `center_penalty`
+/// 兩者依局面階段縮放：兵力齊全時為全額，純殘局時為 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KingSafetyWeights {
+    pub edge_bonus: i32,
+    pub center_penalty: i32,
+}
+
+impl Default for PieceValues {
+    fn default() -> Self {
+        PieceValues {
+            king: piece_value(PieceType::King),
+            queen: piece_value(PieceType::Queen),
+            rook: piece_value(PieceType::Rook),
+            bishop: piece_value(PieceType::Bishop),
+            knight: piece_value(PieceType::Knight),
+            pawn: piece_value(PieceType::Pawn),
+            custom: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for KingSafetyWeights {
+    fn default() -> Self {
+        KingSafetyWeights { edge_bonus: 1, center_penalty: 1 }
+    }
+}
+
+impl PieceValues {
+    /// 棋種的物料分數
+    pub fn value(&self, kind: PieceType) -> i32 {
+        match kind {
+            PieceType::King => self.king,
+            PieceType::Queen => self.queen,
+            PieceType::Rook => self.rook,
+            PieceType::Bishop => self.bishop,
+            PieceType::Knight => self.knight,
+            PieceType::Pawn => self.pawn,
+            PieceType::Custom(id) => self.custom.get(&id).copied().unwrap_or_else(|| piece_value(kind)),
+        }
+    }
+}
+
+impl EvalParams {
+    /// 從 TOML 文字解析
+    pub fn from_toml(text: &str) -> Result<Self, EvalParamsError> {
+        Ok(toml::from_str(text)?)
+    }
+
+    /// 從 JSON 文字解析
+    pub fn from_json(text: &str) -> Result<Self, EvalParamsError> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    /// 從檔案載入，依副檔名（`.toml` 或 `.json`）決定格式
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EvalParamsError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml(&text),
+            Some("json") => Self::from_json(&text),
+            _ => Err(EvalParamsError::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+}
+
+/// 載入評估參數時的錯誤
+#[derive(Debug)]
+pub enum EvalParamsError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    /// 副檔名不是 `.toml` 或 `.json`
+    UnsupportedFormat(String),
+}
+
+impl fmt::Display for EvalParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalParamsError::Io(e) => write!(f, "無法讀取評估參數檔：{}", e),
+            EvalParamsError::Toml(e) => write!(f, "TOML 格式錯誤：{}", e),
+            EvalParamsError::Json(e) => write!(f, "JSON 格式錯誤：{}", e),
+            EvalParamsError::UnsupportedFormat(path) => write!(f, "不支援的評估參數檔格式：{}", path),
+        }
+    }
+}
+
+impl std::error::Error for EvalParamsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EvalParamsError::Io(e) => Some(e),
+            EvalParamsError::Toml(e) => Some(e),
+            EvalParamsError::Json(e) => Some(e),
+            EvalParamsError::UnsupportedFormat(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for EvalParamsError {
+    fn from(e: io::Error) -> Self {
+        EvalParamsError::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for EvalParamsError {
+    fn from(e: toml::de::Error) -> Self {
+        EvalParamsError::Toml(e)
+    }
+}
+
+impl From<serde_json::Error> for EvalParamsError {
+    fn from(e: serde_json::Error) -> Self {
+        EvalParamsError::Json(e)
+    }
+}
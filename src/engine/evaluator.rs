@@ -1,104 +1,191 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
 use crate::engine::board::Board;
+use crate::engine::eval_params::EvalParams;
 use crate::engine::piece::Piece;
-use crate::engine::types::{PieceType, Color};
+use crate::engine::pst::{game_phase, PHASE_TOTAL};
+use crate::engine::types::{PieceType, Color, Position};
+
+/// 棋種的預設物料分數（王不計分，自訂棋種以編號作為分數），也是 `Evaluator::piece_value` 的預設值
+pub fn piece_value(kind: PieceType) -> i32 {
+    match kind {
+        PieceType::King => 0,
+        PieceType::Queen => 9,
+        PieceType::Rook => 5,
+        PieceType::Bishop => 3,
+        PieceType::Knight => 3,
+        PieceType::Pawn => 1,
+        PieceType::Custom(v) => v as i32,
+    }
+}
 
-/// 靜態評估函式：正值表示白方優勢，負值表示黑方優勢
-/// 評分依據包含：物料分數 + 位置獎勵（中心控制）+ 王的安全性
-pub fn evaluate_board(board: &Board) -> i32 {
-    let mut score = 0;
-    let center_bonus: i32 = 1; // 中心格額外加分
-
-    let center_x = board.width as i32 / 2;
-    let center_y = board.height as i32 / 2;
-
-    for piece in board.pieces.values() {
-        let base_value = match piece.kind {
-            PieceType::King => 0,
-            PieceType::Queen => 9,
-            PieceType::Rook => 5,
-            PieceType::Bishop => 3,
-            PieceType::Knight => 3,
-            PieceType::Pawn => 1,
-            PieceType::Custom(v) => v as i32,
-        };
+/// 局面評估介面：搜尋對評估函式是泛型的，可以換成自己的評估（只算物料、殘局專用、學習得到的模型等）
+/// 分數一律以白方為正；多執行緒搜尋會在各執行緒共用同一個評估器，所以需要 `Send + Sync`
+pub trait Evaluator: Send + Sync {
+    fn evaluate(&self, board: &Board) -> i32;
 
-        // 簡單位置加分（越靠近中心越好）
-        // 中央控制有助於移動、牽制敵方，特別在殘局中更有效
-        let (x, y) = (piece.pos.0 as i32, piece.pos.1 as i32);
-        let dist_x = (center_x - x).abs();
-        let dist_y = (center_y - y).abs();
-        let positional_bonus = center_bonus.saturating_sub(dist_x + dist_y);
+    /// 棋種的物料分數，須與 `evaluate` 同一尺度：搜尋以此排序吃子（MVV-LVA）、估計吃子的收益（delta 剪枝），
+    /// 並以兵的分數換算各種以兵為單位的邊際。預設為 `piece_value`
+    fn piece_value(&self, kind: PieceType) -> i32 {
+        piece_value(kind)
+    }
+}
 
-        // 王的安全性：靠邊更安全（簡化模型）
-        let king_safety_bonus = if piece.kind == PieceType::King {
-            let near_edge = x == 0 || y == 0 || x == board.width as i32 - 1 || y == board.height as i32 - 1;
-            if near_edge { 1 } else { -1 }
-        } else {
-            0
-        };
+/// 預設評估：物料分數 + 位置分數表（依局面階段在中局與殘局表之間漸變）+ 王的安全性（只在中局計分），
+/// 各項權重由 `params` 決定
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DefaultEvaluator {
+    pub params: EvalParams,
+}
 
-        let total_value = base_value + positional_bonus + king_safety_bonus;
+impl DefaultEvaluator {
+    pub fn new(params: EvalParams) -> Self {
+        DefaultEvaluator { params }
+    }
 
-        score += match piece.color {
-            Color::White => total_value,
-            Color::Black => -total_value,
+    /// 評分明細：每個棋子各項的分數與各項的總和
+    pub fn breakdown(&self, board: &Board) -> EvalBreakdown {
+        let phase = game_phase(board);
+        let mut pieces: Vec<PieceEval> =
+            board.pieces.values().map(|piece| PieceEval::of(board, piece, &self.params, phase)).collect();
+        // 棋子依位置排序，明細的順序只取決於局面
+        pieces.sort_by_key(|p| (p.pos.1, p.pos.0));
+
+        let mut terms = EvalTerms::default();
+        for piece in &pieces {
+            let sign = piece.sign();
+            terms.material += sign * piece.material;
+            terms.positional += sign * piece.positional;
+            terms.king_safety += sign * piece.king_safety;
         }
+        EvalBreakdown { total: terms.total(), terms, pieces }
     }
-    score
 }
 
-pub fn evaluate_board_verbose(board: &Board) -> i32 {
-    let mut score = 0;
-    let center_bonus: i32 = 1;
+impl Evaluator for DefaultEvaluator {
+    fn evaluate(&self, board: &Board) -> i32 {
+        let phase = game_phase(board);
+        board.pieces.values().map(|piece| PieceEval::of(board, piece, &self.params, phase).total).sum()
+    }
 
-    let center_x = board.width as i32 / 2;
-    let center_y = board.height as i32 / 2;
+    fn piece_value(&self, kind: PieceType) -> i32 {
+        self.params.pieces.value(kind)
+    }
+}
+
+/// 只計算物料的評估
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaterialEvaluator;
+
+impl Evaluator for MaterialEvaluator {
+    fn evaluate(&self, board: &Board) -> i32 {
+        board
+            .pieces
+            .values()
+            .map(|piece| match piece.color {
+                Color::White => piece_value(piece.kind),
+                Color::Black => -piece_value(piece.kind),
+            })
+            .sum()
+    }
+}
 
-    println!("棋子評分明細：");
+/// 預設評估中單一棋子的各項分數（各項以該棋子自己為正，`total` 以白方為正）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PieceEval {
+    pub kind: PieceType,
+    pub color: Color,
+    pub pos: Position,
+    pub material: i32,
+    pub positional: i32,
+    pub king_safety: i32,
+    pub total: i32,
+}
 
-    for piece in board.pieces.values() {
-        let base_value = match piece.kind {
-            PieceType::King => 0,
-            PieceType::Queen => 9,
-            PieceType::Rook => 5,
-            PieceType::Bishop => 3,
-            PieceType::Knight => 3,
-            PieceType::Pawn => 1,
-            PieceType::Custom(v) => v as i32,
-        };
+impl PieceEval {
+    fn of(board: &Board, piece: &Piece, params: &EvalParams, phase: i32) -> Self {
+        // 位置分數：依棋盤大小重新取樣的位置分數表，依局面階段在中局與殘局之間漸變
+        let positional = params.positional.value(board, piece.kind, piece.color, piece.pos, phase);
 
         let (x, y) = (piece.pos.0 as i32, piece.pos.1 as i32);
-        let dist_x = (center_x - x).abs();
-        let dist_y = (center_y - y).abs();
-        let positional_bonus = center_bonus.saturating_sub(dist_x + dist_y);
 
-        let king_safety_bonus = if piece.kind == PieceType::King {
+        // 王的安全性：靠邊更安全（簡化模型），依局面階段縮放，殘局時王應該走向中央而不再計分
+        let king_safety = if piece.kind == PieceType::King {
             let near_edge = x == 0 || y == 0 || x == board.width as i32 - 1 || y == board.height as i32 - 1;
-            if near_edge { 1 } else { -1 }
+            let base = if near_edge { params.king_safety.edge_bonus } else { -params.king_safety.center_penalty };
+            (base as f32 * phase.clamp(0, PHASE_TOTAL) as f32 / PHASE_TOTAL as f32).round() as i32
         } else {
             0
         };
 
-        let total = base_value + positional_bonus + king_safety_bonus;
-        let signed = match piece.color {
-            Color::White => total,
-            Color::Black => -total,
+        let mut eval = PieceEval {
+            kind: piece.kind,
+            color: piece.color,
+            pos: piece.pos,
+            material: params.pieces.value(piece.kind),
+            positional,
+            king_safety,
+            total: 0,
         };
+        eval.total = eval.sign() * (eval.material + eval.positional + eval.king_safety);
+        eval
+    }
+
+    /// 白方為 1、黑方為 -1
+    fn sign(&self) -> i32 {
+        match self.color {
+            Color::White => 1,
+            Color::Black => -1,
+        }
+    }
+}
+
+/// 各評分項目的總和（白方為正）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct EvalTerms {
+    pub material: i32,
+    pub positional: i32,
+    pub king_safety: i32,
+}
 
-        println!(
-            "- {:?} at ({}, {}): base={}, pos_bonus={}, king_safe={}, total={} [{}]",
-            piece.kind, piece.pos.0, piece.pos.1,
-            base_value, positional_bonus, king_safety_bonus,
-            signed,
-            match piece.color {
-                Color::White => "White",
-                Color::Black => "Black",
-            }
-        );
-
-        score += signed;
+impl EvalTerms {
+    pub fn total(&self) -> i32 {
+        self.material + self.positional + self.king_safety
     }
+}
+
+/// 結構化的評分明細，可序列化、顯示在介面上或在測試中檢查
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvalBreakdown {
+    /// 總分（白方為正），等於 `evaluate_board`
+    pub total: i32,
+    pub terms: EvalTerms,
+    /// 每個棋子的明細，依位置（先列後行）排序
+    pub pieces: Vec<PieceEval>,
+}
+
+impl fmt::Display for EvalBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "棋子評分明細：")?;
+        for p in &self.pieces {
+            writeln!(
+                f,
+                "- {:?} at ({}, {}): base={}, pos_bonus={}, king_safe={}, total={} [{:?}]",
+                p.kind, p.pos.0, p.pos.1, p.material, p.positional, p.king_safety, p.total, p.color
+            )?;
+        }
+        write!(f, "總分：{}", self.total)
+    }
+}
+
+/// 靜態評估函式（預設評估、預設參數）：正值表示白方優勢，負值表示黑方優勢
+pub fn evaluate_board(board: &Board) -> i32 {
+    DefaultEvaluator::default().evaluate(board)
+}
 
-    println!("總分：{}", score);
-    score
+/// 預設評估的評分明細，需要文字時可直接以 `Display` 輸出
+pub fn evaluate_board_verbose(board: &Board) -> EvalBreakdown {
+    DefaultEvaluator::default().breakdown(board)
 }
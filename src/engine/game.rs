@@ -1,7 +1,17 @@
+use std::sync::Arc;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
 use crate::engine::board::Board;
-use crate::engine::piece::Piece;
-use crate::engine::types::{Color, Position};
-use crate::engine::movement::get_legal_moves;
+use crate::engine::evaluator::{DefaultEvaluator, EvalBreakdown, Evaluator};
+use crate::engine::moves::Move;
+use crate::engine::types::{Color, PieceType, Position};
+use crate::engine::castling::CastlingRights;
+use crate::engine::minmax::{SearchLimits, SearchResult, Searcher, DEFAULT_TT_ENTRIES};
+use crate::engine::movement::{get_legal_move_list, has_legal_moves, is_in_check};
+use crate::engine::skill::{skill_search, SkillLevel};
+use crate::engine::zobrist::position_hash;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TurnState {
@@ -25,9 +35,32 @@ impl TurnState {
     }
 }
 
+/// 一方由誰操控：玩家，或指定棋力等級的電腦
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Player {
+    Human,
+    Engine(SkillLevel),
+}
+
+impl Player {
+    pub fn is_engine(self) -> bool {
+        matches!(self, Player::Engine(_))
+    }
+}
+
 pub struct GameState {
     pub board: Board,
     pub turn: TurnState,
+    /// 白方的操控者，預設為玩家
+    pub white: Player,
+    /// 黑方的操控者，預設為最強的電腦
+    pub black: Player,
+    /// 評分與電腦搜尋使用的評估（可換成從檔案載入的參數）
+    pub evaluator: Arc<DefaultEvaluator>,
+    /// 電腦在候選走法中挑選時使用的亂數產生器，換成固定種子即可重現電腦的對局
+    pub rng: StdRng,
+    /// 電腦使用的搜尋器，置換表在整盤棋中沿用
+    pub searcher: Searcher,
 }
 
 impl GameState {
@@ -35,76 +68,149 @@ impl GameState {
         Self {
             board,
             turn: TurnState::White,
+            white: Player::Human,
+            black: Player::Engine(SkillLevel::default()),
+            evaluator: Arc::new(DefaultEvaluator::default()),
+            rng: StdRng::from_os_rng(),
+            searcher: Searcher::new(DEFAULT_TT_ENTRIES),
+        }
+    }
+
+    /// 以固定種子重設電腦使用的亂數產生器
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// 目前局面的評分（白方為正）
+    pub fn evaluate(&self) -> i32 {
+        self.evaluator.evaluate(&self.board)
+    }
+
+    /// 目前局面的評分明細
+    pub fn breakdown(&self) -> EvalBreakdown {
+        self.evaluator.breakdown(&self.board)
+    }
+
+    /// `color` 一方的操控者
+    pub fn player(&self, color: Color) -> Player {
+        match color {
+            Color::White => self.white,
+            Color::Black => self.black,
+        }
+    }
+
+    pub fn set_player(&mut self, color: Color, player: Player) {
+        match color {
+            Color::White => self.white = player,
+            Color::Black => self.black = player,
+        }
+    }
+
+    /// 輪到電腦時回傳其棋力等級，輪到玩家時為 None
+    pub fn engine_to_move(&self) -> Option<SkillLevel> {
+        match self.player(self.turn.as_color()) {
+            Player::Engine(level) => Some(level),
+            Player::Human => None,
         }
     }
 
-    /// 嘗試從 `from` 移動到 `to`
+    /// 嘗試從 `from` 移動到 `to`，兵走到底線時需以 `promotion` 指定升變棋種
     /// 若合法則執行移動並切換回合，否則回傳錯誤訊息
-    pub fn try_move(&mut self, from: Position, to: Position) -> Result<(), &'static str> {
-        // 不保留對 piece 的 reference，只拿出 id 與 color
-        let piece_info = self.board.get_piece_at(from)
-            .map(|p| (p.id, p.color))
-            .ok_or("來源格沒有棋子")?;
-    
-        if piece_info.1 != self.turn.as_color() {
+    pub fn try_move(&mut self, from: Position, to: Position, promotion: Option<PieceType>) -> Result<(), &'static str> {
+        let piece = self.board.get_piece_at(from).ok_or("來源格沒有棋子")?;
+
+        if piece.color != self.turn.as_color() {
             return Err("不是你的回合");
         }
-    
-        let piece = self.board.pieces.get(&piece_info.0).unwrap();
-        let legal_moves = get_legal_moves(&self.board, piece);
-    
-        if !legal_moves.contains(&to) {
-            return Err("非法走法");
-        }
-    
-        self.board.remove_piece_at(to);
-        self.board.move_piece(piece_info.0, to);
+
+        let candidates: Vec<Move> = get_legal_move_list(&self.board, piece)
+            .into_iter()
+            .filter(|m| m.to == to)
+            .collect();
+
+        let mv = match candidates.as_slice() {
+            [] => return Err("非法走法"),
+            [only] if only.promotion.is_none() => *only,
+            _ => match promotion {
+                None => return Err("請選擇升變棋種"),
+                Some(kind) => *candidates.iter()
+                    .find(|m| m.promotion == Some(kind))
+                    .ok_or("非法升變棋種")?,
+            },
+        };
+
+        self.board.make_move(&mv);
         self.turn = self.turn.switch();
-    
+
         Ok(())
-    }    
+    }
 
-    /// 如果是電腦回合（黑方），自動執行一步最佳走法
-    /// 回傳是否成功下棋（Some），或無合法走法（None）
-    pub fn try_ai_move(&mut self, depth: usize) -> Option<(Position, Position)> {
-        use crate::engine::minmax::choose_best_move;
+    /// 如果輪到電腦操控的一方，在 `limits` 限制內以該方的棋力等級搜尋並執行一步
+    /// 回傳搜尋結果（其中 `best_move` 即執行的走法）；輪到玩家或無合法走法時為 None
+    pub fn try_ai_move(&mut self, limits: SearchLimits) -> Option<SearchResult> {
+        let level = self.engine_to_move()?;
+        self.searcher.set_evaluator(self.evaluator.clone());
+        let result = skill_search(&mut self.searcher, &self.board, self.turn.as_color(), level, limits, &mut self.rng, |_| {});
+        let best = result.best_move?;
 
-        if self.turn != TurnState::Black {
-            return None;
-        }
+        self.board.make_move(&best);
+        self.turn = self.turn.switch();
 
-        let best = choose_best_move(&self.board, Color::Black, depth)?;
-        let (from, to) = best;
+        Some(result)
+    }
 
-        let piece_info = self.board.get_piece_at(from)
-            .map(|p| (p.id, p.color))?;
+    /// 目前局面的 Zobrist 雜湊值（含輪到哪一方）
+    pub fn hash(&self) -> u64 {
+        position_hash(&self.board, self.turn.as_color())
+    }
 
-        self.board.remove_piece_at(to);
-        self.board.move_piece(piece_info.0, to);
-        self.turn = self.turn.switch();
+    /// 目前的王車易位權利
+    pub fn castling_rights(&self) -> CastlingRights {
+        self.board.castling
+    }
+
+    /// 目前可吃過路兵的目標格（上一步對方兵前進兩格時才有）
+    pub fn en_passant_target(&self) -> Option<Position> {
+        self.board.en_passant
+    }
 
-        Some((from, to))
+    /// 目前輪到的一方是否被將軍
+    pub fn is_in_check(&self) -> bool {
+        is_in_check(&self.board, self.turn.as_color())
+    }
+
+    /// 目前對局結果（尚未結束則為 None）
+    pub fn result(&self) -> Option<GameResult> {
+        game_result(&self.board, self.turn.as_color())
     }
 }
 
-pub fn check_game_end(board: &Board) -> Option<&'static str> {
-    let mut white_king_exists = false;
-    let mut black_king_exists = false;
-
-    for piece in board.pieces.values() {
-        use crate::engine::types::PieceType::*;
-        if piece.kind == King {
-            match piece.color {
-                crate::engine::types::Color::White => white_king_exists = true,
-                crate::engine::types::Color::Black => black_king_exists = true,
-            }
-        }
+/// 對局結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameResult {
+    /// 將死：被將軍且無合法走法
+    Checkmate { winner: Color },
+    /// 逼和：未被將軍但無合法走法
+    Stalemate,
+}
+
+/// 判斷輪到 `side_to_move` 時對局是否已結束
+pub fn game_result(board: &Board, side_to_move: Color) -> Option<GameResult> {
+    if has_legal_moves(board, side_to_move) {
+        return None;
     }
+    if is_in_check(board, side_to_move) {
+        Some(GameResult::Checkmate { winner: side_to_move.opposite() })
+    } else {
+        Some(GameResult::Stalemate)
+    }
+}
 
-    match (white_king_exists, black_king_exists) {
-        (true, true) => None,
-        (false, true) => Some("黑方勝利！"),
-        (true, false) => Some("白方勝利！"),
-        (false, false) => Some("雙方國王都不見了？！？！"),
+/// 回傳對局結束訊息（供介面顯示），尚未結束則為 None
+pub fn check_game_end(board: &Board, side_to_move: Color) -> Option<&'static str> {
+    match game_result(board, side_to_move)? {
+        GameResult::Checkmate { winner: Color::White } => Some("將死，白方勝利！"),
+        GameResult::Checkmate { winner: Color::Black } => Some("將死，黑方勝利！"),
+        GameResult::Stalemate => Some("逼和，平手！"),
     }
 }
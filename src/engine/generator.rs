@@ -1,10 +1,15 @@
+use std::collections::HashSet;
+
 use crate::engine::board::Board;
+use crate::engine::castling::CastlingRights;
 use crate::engine::piece::Piece;
-use crate::engine::types::{Color, PieceType, Tile, Position};
+use crate::engine::types::{Color, PieceType, Tile};
 use crate::engine::utils::random_position;
 use crate::engine::evaluator::evaluate_board;
+use crate::engine::minmax::{SearchLimits, SearchOptions, SearchResult, Searcher};
+use crate::engine::movement::is_in_check;
 
-use rand::seq::{IndexedRandom, SliceRandom};
+use rand::seq::IndexedRandom;
 use rand::Rng;
 
 
@@ -15,14 +20,25 @@ pub fn generate_random_board(
     num_white: usize,
     num_black: usize,
     num_blocked: usize,
+) -> Board {
+    generate_random_board_with(&mut rand::rng(), width, height, num_white, num_black, num_blocked)
+}
+
+/// 同 `generate_random_board`，但使用指定的亂數產生器（傳入固定種子即可重現同一個殘局）
+pub fn generate_random_board_with(
+    rng: &mut impl Rng,
+    width: usize,
+    height: usize,
+    num_white: usize,
+    num_black: usize,
+    num_blocked: usize,
 ) -> Board {
     let mut board = Board::new(width, height);
-    let mut rng = rand::thread_rng();
 
     // 放置禁區
     let mut blocked_count = 0;
     while blocked_count < num_blocked {
-        let pos = random_position(width, height);
+        let pos = random_position(rng, width, height);
         if board.tiles[pos.0][pos.1] == Tile::Empty {
             board.set_blocked(pos);
             blocked_count += 1;
@@ -31,7 +47,7 @@ pub fn generate_random_board(
 
     // 放置白王
     loop {
-        let pos = random_position(width, height);
+        let pos = random_position(rng, width, height);
         if board.is_tile_free(pos) {
             board.add_piece(Piece {
                 id: 0,
@@ -45,7 +61,7 @@ pub fn generate_random_board(
 
     // 放置黑王
     loop {
-        let pos = random_position(width, height);
+        let pos = random_position(rng, width, height);
         if board.is_tile_free(pos) {
             board.add_piece(Piece {
                 id: 0,
@@ -69,9 +85,9 @@ pub fn generate_random_board(
     // 放置白棋（不包含王）
     let mut placed = 1; // 已放置白王
     while placed < num_white {
-        let pos = random_position(width, height);
+        let pos = random_position(rng, width, height);
         if board.is_tile_free(pos) {
-            let piece_type = *piece_pool.choose(&mut rng).unwrap();
+            let piece_type = *piece_pool.choose(rng).unwrap();
             board.add_piece(Piece {
                 id: 0,
                 kind: piece_type,
@@ -85,9 +101,9 @@ pub fn generate_random_board(
     // 放置黑棋（不包含王）
     let mut placed = 1; // 已放置黑王
     while placed < num_black {
-        let pos = random_position(width, height);
+        let pos = random_position(rng, width, height);
         if board.is_tile_free(pos) {
-            let piece_type = *piece_pool.choose(&mut rng).unwrap();
+            let piece_type = *piece_pool.choose(rng).unwrap();
             board.add_piece(Piece {
                 id: 0,
                 kind: piece_type,
@@ -101,7 +117,69 @@ pub fn generate_random_board(
     board
 }
 
-/// 重複生成直到符合指定難度閾值（例如對白方不利）
+/// 產生 `count` 個互不相同的隨機殘局（以 Zobrist 雜湊去除重複局面）
+/// 可能的局面數少於 `count` 時會一直重試，呼叫端需自行確保參數合理
+pub fn generate_unique_boards(
+    count: usize,
+    width: usize,
+    height: usize,
+    num_white: usize,
+    num_black: usize,
+    num_blocked: usize,
+) -> Vec<Board> {
+    let mut seen = HashSet::new();
+    let mut boards = Vec::with_capacity(count);
+    while boards.len() < count {
+        let board = generate_random_board(width, height, num_white, num_black, num_blocked);
+        if seen.insert(board.hash()) {
+            boards.push(board);
+        }
+    }
+    boards
+}
+
+/// 產生標準開局擺法（寬度至少 5、高度至少 4）：
+/// 車在兩個角落、王在中間偏右、后在王的左邊，其餘底線格由兩側往內交替放騎士與主教，
+/// 兵排在第二列；會開啟兵前進兩格並設定王車易位權利。棋盤小於最低尺寸時 panic
+pub fn generate_standard_board(width: usize, height: usize) -> Board {
+    assert!(width >= 5 && height >= 4, "標準開局至少需要 5x4 的棋盤，實際為 {}x{}", width, height);
+    let mut board = Board::new(width, height);
+    board.pawn_double_step = true;
+
+    let back_rank: Vec<PieceType> = (0..width).map(|x| standard_back_rank(x, width)).collect();
+
+    for (color, back_y, pawn_y) in [
+        (Color::White, height - 1, height - 2),
+        (Color::Black, 0, 1),
+    ] {
+        for (x, &kind) in back_rank.iter().enumerate() {
+            board.add_piece(Piece { id: 0, kind, color, pos: (x, back_y) });
+            board.add_piece(Piece { id: 0, kind: PieceType::Pawn, color, pos: (x, pawn_y) });
+        }
+    }
+
+    board.castling = CastlingRights::detect(&board);
+    board
+}
+
+/// 標準開局底線第 `x` 格的棋種
+fn standard_back_rank(x: usize, width: usize) -> PieceType {
+    let king_x = width / 2;
+    if x == 0 || x == width - 1 {
+        PieceType::Rook
+    } else if x == king_x {
+        PieceType::King
+    } else if x == king_x - 1 {
+        PieceType::Queen
+    } else {
+        // 從最近的角落往內數：奇數格放騎士、偶數格放主教
+        let from_corner = if x < king_x { x } else { width - 1 - x };
+        if from_corner % 2 == 1 { PieceType::Knight } else { PieceType::Bishop }
+    }
+}
+
+/// 重複生成直到符合難度條件（見 `meets_difficulty`）：評分不超過 `threshold`（例如對白方不利），
+/// `mate_in` 為 Some(N) 時還要白方先走、最短恰好 N 步能將死黑方
 pub fn generate_by_difficulty(
     width: usize,
     height: usize,
@@ -109,12 +187,51 @@ pub fn generate_by_difficulty(
     num_black: usize,
     num_blocked: usize,
     threshold: i32,
+    mate_in: Option<usize>,
 ) -> Board {
+    let mut rng = rand::rng();
     loop {
-        let board = generate_random_board(width, height, num_white, num_black, num_blocked);
-        let score = evaluate_board(&board);
-        if score <= threshold {
+        let board = generate_random_board_with(&mut rng, width, height, num_white, num_black, num_blocked);
+        if meets_difficulty(&board, threshold, mate_in) {
             return board;
         }
     }
 }
+
+/// 棋盤是否符合 `generate_by_difficulty` 的難度條件
+pub fn meets_difficulty(board: &Board, threshold: i32, mate_in: Option<usize>) -> bool {
+    evaluate_board(board) <= threshold && mate_in.is_none_or(|n| find_mate_in(board, n).is_some())
+}
+
+/// 重複生成直到白方先走、最短恰好 `mate_in` 步能將死黑方
+/// 回傳棋盤與搜尋結果（主要變例即為解答）；隨機局面很少有長的將死，`mate_in` 越大需要越久
+pub fn generate_mate_puzzle(
+    width: usize,
+    height: usize,
+    num_white: usize,
+    num_black: usize,
+    num_blocked: usize,
+    mate_in: usize,
+) -> (Board, SearchResult) {
+    let mut rng = rand::rng();
+    loop {
+        let board = generate_random_board_with(&mut rng, width, height, num_white, num_black, num_blocked);
+        if let Some(result) = find_mate_in(&board, mate_in) {
+            return (board, result);
+        }
+    }
+}
+
+/// 白方先走、最短恰好 `mate_in` 步能將死黑方時回傳搜尋結果，否則為 None
+/// 輪到白方時黑方已被將軍的局面不合法，也回傳 None
+/// 空著剪枝與 LMR 可能漏掉將死，因此以 `SearchOptions::exact()` 搜尋
+pub fn find_mate_in(board: &Board, mate_in: usize) -> Option<SearchResult> {
+    assert!(mate_in > 0, "至少要一步將死");
+    if is_in_check(board, Color::Black) {
+        return None;
+    }
+    let mut searcher = Searcher::new(1 << 14);
+    searcher.options = SearchOptions::exact();
+    let result = searcher.search(board, Color::White, SearchLimits::depth(mate_in * 2 - 1));
+    (result.mate_in() == Some(mate_in as i32)).then_some(result)
+}
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use super::board::Board;
+use super::evaluator::piece_value;
+use super::movement::{generate_legal_moves, is_in_check};
+use super::moves::Move;
+use super::ordering::order_captures;
+use super::types::Color;
+use super::zobrist::position_hash;
+
+/// 將死解答：主要變例與所有能達成同樣步數將死的第一步
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MateSolution {
+    /// 攻方最快的將死、守方最頑強的抵抗，最後一步為將死
+    pub line: Vec<Move>,
+    /// 所有能在同樣步數內強制將死的第一步（關鍵著）
+    pub key_moves: Vec<Move>,
+}
+
+impl MateSolution {
+    /// 將死所需的步數（攻方走的步數）
+    pub fn moves(&self) -> usize {
+        self.line.len().div_ceil(2)
+    }
+
+    /// 關鍵著是否唯一（可發表的殘局題目需要唯一解）
+    pub fn is_unique(&self) -> bool {
+        self.key_moves.len() == 1
+    }
+}
+
+/// 證明 `side` 先走能在 `n` 步內強制將死，並回傳最短的將死主要變例
+/// 會窮舉守方所有應著，不使用評分，因此結果是嚴格的證明
+pub fn solve_mate(board: &Board, side: Color, n: usize) -> Option<Vec<Move>> {
+    analyze_mate(board, side, n).map(|solution| solution.line)
+}
+
+/// 同 `solve_mate`，另外列出所有關鍵著以判斷解答是否唯一
+pub fn analyze_mate(board: &Board, side: Color, n: usize) -> Option<MateSolution> {
+    let mut solver = MateSolver { attacker: side, memo: HashMap::new() };
+    let mut work = board.clone();
+
+    // 由短到長找，確保得到最短的將死
+    let moves = (1..=n).find(|&k| solver.attacker_mates(&mut work, k))?;
+    let key_moves = solver
+        .sorted_moves(&mut work, side)
+        .into_iter()
+        .filter(|mv| solver.mates_after(&mut work, mv, moves))
+        .collect();
+    let line = solver.main_line(&mut work, moves);
+    Some(MateSolution { line, key_moves })
+}
+
+struct MateSolver {
+    attacker: Color,
+    /// (局面雜湊, 剩餘步數) -> 攻方能否將死
+    memo: HashMap<(u64, usize), bool>,
+}
+
+impl MateSolver {
+    /// 依固定順序排列合法走法，讓解答只取決於局面
+    fn sorted_moves(&self, board: &mut Board, side: Color) -> Vec<Move> {
+        let mut moves = generate_legal_moves(board, side);
+        order_captures(board, &mut moves, piece_value);
+        moves
+    }
+
+    /// 輪到攻方：是否存在一步棋讓守方在 `n` 步內無論如何都被將死
+    fn attacker_mates(&mut self, board: &mut Board, n: usize) -> bool {
+        if n == 0 {
+            return false;
+        }
+        let key = (position_hash(board, self.attacker), n);
+        if let Some(&known) = self.memo.get(&key) {
+            return known;
+        }
+
+        let found = self
+            .sorted_moves(board, self.attacker)
+            .iter()
+            .any(|mv| self.mates_after(board, mv, n));
+        self.memo.insert(key, found);
+        found
+    }
+
+    /// 攻方走 `mv` 之後，守方是否所有應著都會在剩下的步數內被將死
+    fn mates_after(&mut self, board: &mut Board, mv: &Move, n: usize) -> bool {
+        let undo = board.make_move(mv);
+        let mated = self.defender_loses(board, n - 1);
+        board.unmake_move(mv, undo);
+        mated
+    }
+
+    /// 輪到守方，攻方還有 `n` 步：無子可動且被將軍即為將死，逼和則不算
+    fn defender_loses(&mut self, board: &mut Board, n: usize) -> bool {
+        let defender = self.attacker.opposite();
+        let replies = self.sorted_moves(board, defender);
+        if replies.is_empty() {
+            return is_in_check(board, defender);
+        }
+        if n == 0 {
+            return false;
+        }
+        replies.iter().all(|reply| {
+            let undo = board.make_move(reply);
+            let mated = self.attacker_mates(board, n);
+            board.unmake_move(reply, undo);
+            mated
+        })
+    }
+
+    /// 守方走完後，攻方最少還需要幾步將死（已知一定在 `n` 步內）
+    fn mate_distance(&mut self, board: &mut Board, n: usize) -> usize {
+        (1..=n).find(|&k| self.attacker_mates(board, k)).unwrap_or(n)
+    }
+
+    /// 主要變例：攻方每次選最快的將死，守方每次選拖最久的應著
+    fn main_line(&mut self, board: &mut Board, n: usize) -> Vec<Move> {
+        let mut line = Vec::new();
+        let mut undos = Vec::new();
+        let mut remaining = n;
+        loop {
+            let key = self
+                .sorted_moves(board, self.attacker)
+                .into_iter()
+                .find(|mv| self.mates_after(board, mv, remaining))
+                .expect("已證明能將死");
+            undos.push(board.make_move(&key));
+            line.push(key);
+
+            let replies = self.sorted_moves(board, self.attacker.opposite());
+            if replies.is_empty() {
+                break;
+            }
+            let mut longest = None;
+            for reply in replies {
+                let undo = board.make_move(&reply);
+                let distance = self.mate_distance(board, remaining - 1);
+                board.unmake_move(&reply, undo);
+                if longest.is_none_or(|(_, best)| distance > best) {
+                    longest = Some((reply, distance));
+                }
+            }
+            let (reply, distance) = longest.unwrap();
+            undos.push(board.make_move(&reply));
+            line.push(reply);
+            remaining = distance;
+        }
+
+        for (mv, undo) in line.iter().zip(undos).rev() {
+            board.unmake_move(mv, undo);
+        }
+        line
+    }
+}
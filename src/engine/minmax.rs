@@ -1,99 +1,949 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
 use crate::engine::board::Board;
-use crate::engine::movement::get_legal_moves;
-use crate::engine::evaluator::evaluate_board;
-use crate::engine::types::{Color, Position};
-use crate::engine::piece::Piece;
-
-/// 電腦選擇最佳移動（含 Alpha-Beta 剪枝）
-pub fn choose_best_move(board: &Board, color: Color, depth: usize) -> Option<(Position, Position)> {
-    let mut best_score = if color == Color::White { i32::MIN } else { i32::MAX };
-    let mut best_move = None;
-
-    for piece in board.pieces.values() {
-        if piece.color != color {
-            continue;
-        }
-
-        let moves = get_legal_moves(board, piece);
-        for to in moves {
-            let mut new_board = board.clone();
-            new_board.remove_piece_at(to);
-            new_board.move_piece(piece.id, to);
-
-            let score = minimax_ab(
-                &new_board,
-                depth - 1,
-                color.opposite(),
-                i32::MIN,
-                i32::MAX,
-            );
-
-            let better = if color == Color::White {
-                score > best_score
+use crate::engine::movement::{any_legal_move, generate_legal_moves, is_in_check};
+use crate::engine::moves::Move;
+use crate::engine::ordering::{order_captures, same_move, MoveOrdering};
+use crate::engine::evaluator::{DefaultEvaluator, Evaluator};
+use crate::engine::tt::{Bound, TranspositionTable, TtStats};
+use crate::engine::types::{Color, PieceType};
+use crate::engine::zobrist::position_hash;
+
+/// 將死分數（遠大於任何物料評分），實際分數會減去到將死為止的層數，越快將死分數越高
+pub const MATE_SCORE: i32 = 100_000;
+
+/// 絕對值不小於此值的分數代表已找到將死
+pub const MATE_BOUND: i32 = MATE_SCORE - 1_000;
+
+/// 搜尋視窗的無限大（大於將死分數，且取負號不會溢位）
+const INFINITY: i32 = 1_000_000;
+
+/// `choose_best_move` 預設的置換表槽位數
+pub const DEFAULT_TT_ENTRIES: usize = 1 << 16;
+
+/// 沒有指定深度時，迭代加深最多搜尋的層數
+pub const MAX_DEPTH: usize = 64;
+
+/// 每搜尋這麼多個節點檢查一次時間
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
+/// 電腦選擇最佳移動（含 Alpha-Beta 剪枝與置換表），兵升變的每種選擇都會被搜尋
+pub fn choose_best_move(board: &Board, color: Color, depth: usize) -> Option<Move> {
+    Searcher::new(DEFAULT_TT_ENTRIES).choose_best_move(board, color, depth)
+}
+
+/// 同 `choose_best_move`，但回傳分數最高的 `count` 個不同第一步，各自附上分數與主要變例
+pub fn choose_top_moves(board: &Board, color: Color, depth: usize, count: usize) -> Vec<PvLine> {
+    Searcher::new(DEFAULT_TT_ENTRIES).top_moves(board, color, depth, count)
+}
+
+/// 搜尋限制：深度、節點數與思考時間，任一項達到就停止
+/// 三項都沒有指定時最多搜尋到 `MAX_DEPTH` 層，大棋盤上可能要很久
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchLimits {
+    pub depth: Option<usize>,
+    pub nodes: Option<u64>,
+    pub time: Option<Duration>,
+    /// 搜尋執行緒數（Lazy SMP），0 或 1 表示單執行緒；節點數限制只計算主執行緒
+    pub threads: usize,
+}
+
+impl SearchLimits {
+    /// 只限制深度
+    pub fn depth(depth: usize) -> Self {
+        SearchLimits { depth: Some(depth), ..Default::default() }
+    }
+
+    /// 只限制節點數
+    pub fn nodes(nodes: u64) -> Self {
+        SearchLimits { nodes: Some(nodes), ..Default::default() }
+    }
+
+    /// 只限制思考時間
+    pub fn time(time: Duration) -> Self {
+        SearchLimits { time: Some(time), ..Default::default() }
+    }
+
+    /// 改用 `threads` 個執行緒搜尋
+    pub fn with_threads(self, threads: usize) -> Self {
+        SearchLimits { threads, ..self }
+    }
+}
+
+/// 搜尋選項
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// 深度用完後是否繼續搜尋吃子（靜態搜尋），關閉時直接使用靜態評分
+    pub quiescence: bool,
+    /// 靜態搜尋最多延伸的層數
+    pub quiescence_depth: usize,
+    /// 靜態搜尋的第一層是否也搜尋將軍的走法
+    pub quiescence_checks: bool,
+    /// Delta 剪枝的安全邊際（以兵的分數為單位）：吃到的物料加上此值仍追不上 alpha，就不搜尋該吃子
+    pub delta_margin: i32,
+    /// 空著剪枝：讓對方連走兩步仍超過 beta 就直接剪枝
+    /// 被將軍、只剩王與兵（容易出現無著可走的局面）或連續空著時不使用
+    pub null_move: bool,
+    /// 空著搜尋減少的層數
+    pub null_move_reduction: usize,
+    /// 空著剪枝前先以減少的深度正常搜尋驗證，避免殘局中無著可走的局面誤剪
+    pub null_move_verification: bool,
+    /// 後段走法縮減：排序靠後的安靜走法先以較淺的深度試探，超過 alpha 才完整重搜
+    pub late_move_reductions: bool,
+    /// 每個節點前幾步不縮減
+    pub lmr_min_moves: usize,
+    /// 主要變例搜尋：第一步以外的走法先用零寬窗口證明不會更好
+    pub pvs: bool,
+    /// 渴望窗口：以上一輪的分數為中心的窄窗口搜尋根節點，失敗時加寬重搜
+    pub aspiration: bool,
+    /// 渴望窗口的初始半寬（以兵的分數為單位）
+    pub aspiration_window: i32,
+    /// MultiPV：每輪迭代找出幾個不同的第一步（1 為一般搜尋）
+    pub multi_pv: usize,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            quiescence: true,
+            quiescence_depth: 8,
+            quiescence_checks: false,
+            delta_margin: 2,
+            null_move: true,
+            null_move_reduction: 2,
+            null_move_verification: true,
+            late_move_reductions: true,
+            lmr_min_moves: 3,
+            pvs: true,
+            aspiration: true,
+            aspiration_window: 1,
+            multi_pv: 1,
+        }
+    }
+}
+
+impl SearchOptions {
+    /// 只保留不影響結果的剪枝（alpha-beta、PVS、渴望窗口），分數與完整的 minimax 相同
+    pub fn exact() -> Self {
+        SearchOptions {
+            null_move: false,
+            late_move_reductions: false,
+            ..Default::default()
+        }
+    }
+}
+
+/// 搜尋結果，分數以搜尋的一方為正
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SearchResult {
+    /// 最佳走法，無合法走法時為 None
+    pub best_move: Option<Move>,
+    pub score: i32,
+    /// 主要變例：預期雙方接下來的走法，第一步就是 `best_move`
+    pub pv: Vec<Move>,
+    /// 完成的迭代深度
+    pub depth: usize,
+    pub nodes: u64,
+    /// 每秒搜尋的節點數
+    pub nps: u64,
+    pub elapsed: Duration,
+    /// 所有候選變例，依分數由高到低排列，第一條與 `best_move`、`score`、`pv` 相同
+    /// 數量為 `SearchOptions::multi_pv`（合法走法不足時較少）
+    pub lines: Vec<PvLine>,
+}
+
+impl SearchResult {
+    /// 若找到將死，回傳步數：正數為搜尋方 N 步內將死對方，負數為 N 步內被將死，0 表示已被將死
+    pub fn mate_in(&self) -> Option<i32> {
+        mate_in(self.score)
+    }
+
+    /// 分數文字：將死顯示為 "#3"（3 步將死）或 "#-2"（2 步內被將死），其餘為數值
+    pub fn score_string(&self) -> String {
+        score_string(self.score)
+    }
+
+    /// 以代數記譜列出主要變例，例如 "e2e4 e7e5 g1f3"
+    pub fn pv_notation(&self, height: usize) -> String {
+        pv_notation(&self.pv, height)
+    }
+}
+
+/// MultiPV 的一條候選變例：第一步、分數（搜尋方為正）與主要變例
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PvLine {
+    pub best_move: Move,
+    pub score: i32,
+    pub pv: Vec<Move>,
+}
+
+impl PvLine {
+    /// 同 `SearchResult::mate_in`
+    pub fn mate_in(&self) -> Option<i32> {
+        mate_in(self.score)
+    }
+
+    /// 同 `SearchResult::score_string`
+    pub fn score_string(&self) -> String {
+        score_string(self.score)
+    }
+
+    /// 同 `SearchResult::pv_notation`
+    pub fn pv_notation(&self, height: usize) -> String {
+        pv_notation(&self.pv, height)
+    }
+}
+
+/// 取消搜尋的旗標，可複製給其他執行緒（例如介面的「停止」按鈕）
+/// 停止後搜尋會盡快回傳目前最好的走法；第一輪迭代仍會完成
+/// 旗標不會自動重設，同一個搜尋器要再次搜尋前需呼叫 `reset`
+#[derive(Debug, Clone, Default)]
+pub struct StopToken(Arc<AtomicBool>);
+
+impl StopToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 要求搜尋停止
+    pub fn stop(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// 清除停止要求
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
+
+/// 搜尋樹中節點的種類，決定能否嘗試空著、是否讀寫置換表
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Node {
+    /// 一般節點
+    Normal,
+    /// 剛走過空著，不再連續走空著
+    AfterNull,
+    /// 空著剪枝的驗證搜尋，與外層節點是同一局面同一層
+    Verify,
+}
+
+/// 搜尋器：持有置換表，連續搜尋時可沿用先前的結果
+/// 多執行緒搜尋時，輔助執行緒各自有一個搜尋器，共用同一張置換表、評估器與停止旗標
+/// 評估函式由型別參數 `E` 決定，預設為 `DefaultEvaluator`
+pub struct Searcher<E: Evaluator = DefaultEvaluator> {
+    pub tt: Arc<TranspositionTable>,
+    evaluator: Arc<E>,
+    pub options: SearchOptions,
+    ordering: MoveOrdering,
+    /// 三角形主要變例表：第 ply 列是從該層開始的最佳走法序列
+    pv_table: Vec<Vec<Move>>,
+    limits: SearchLimits,
+    deadline: Option<Instant>,
+    nodes: u64,
+    /// 至少完成一輪迭代後才允許中止，確保一定有走法可回傳
+    can_stop: bool,
+    stopped: bool,
+    /// 主執行緒搜尋結束時設定，通知輔助執行緒停止
+    stop_signal: Arc<AtomicBool>,
+    /// 外部的取消要求
+    cancel: StopToken,
+}
+
+impl Searcher {
+    /// 以 `tt_entries` 個槽位的置換表建立使用預設評估的搜尋器
+    pub fn new(tt_entries: usize) -> Self {
+        Self::with_evaluator(tt_entries, DefaultEvaluator::default())
+    }
+
+    /// 使用既有的置換表建立搜尋器（多個搜尋器可共用同一張表）
+    pub fn with_table(tt: Arc<TranspositionTable>) -> Self {
+        Self::with_parts(tt, Arc::new(DefaultEvaluator::default()))
+    }
+}
+
+impl<E: Evaluator> Searcher<E> {
+    /// 以 `tt_entries` 個槽位的置換表建立使用 `evaluator` 評估局面的搜尋器
+    pub fn with_evaluator(tt_entries: usize, evaluator: E) -> Self {
+        Self::with_parts(Arc::new(TranspositionTable::new(tt_entries)), Arc::new(evaluator))
+    }
+
+    /// 使用既有的置換表與評估器建立搜尋器
+    pub fn with_parts(tt: Arc<TranspositionTable>, evaluator: Arc<E>) -> Self {
+        Searcher {
+            tt,
+            evaluator,
+            options: SearchOptions::default(),
+            ordering: MoveOrdering::new(),
+            pv_table: Vec::new(),
+            limits: SearchLimits::default(),
+            deadline: None,
+            nodes: 0,
+            can_stop: false,
+            stopped: false,
+            stop_signal: Arc::new(AtomicBool::new(false)),
+            cancel: StopToken::new(),
+        }
+    }
+
+    /// 搜尋使用的評估器
+    pub fn evaluator(&self) -> &E {
+        &self.evaluator
+    }
+
+    /// 換用另一個評估器；置換表中的分數來自原本的評估器，因此一併清空
+    /// 傳入的就是目前的評估器時不做任何事
+    pub fn set_evaluator(&mut self, evaluator: Arc<E>) {
+        if !Arc::ptr_eq(&self.evaluator, &evaluator) {
+            self.evaluator = evaluator;
+            self.tt.clear();
+        }
+    }
+
+    /// 取得這個搜尋器的取消旗標，可在其他執行緒呼叫 `stop` 中止搜尋
+    pub fn stop_token(&self) -> StopToken {
+        self.cancel.clone()
+    }
+
+    /// 建立輔助執行緒用的搜尋器：共用置換表、評估器、選項與停止旗標，可隨時被停止
+    fn helper(&self) -> Searcher<E> {
+        Searcher {
+            options: self.options,
+            can_stop: true,
+            stop_signal: self.stop_signal.clone(),
+            ..Searcher::with_parts(self.tt.clone(), self.evaluator.clone())
+        }
+    }
+
+    /// 搜尋 `depth` 層並回傳 `color` 的最佳走法，無合法走法時為 None
+    pub fn choose_best_move(&mut self, board: &Board, color: Color, depth: usize) -> Option<Move> {
+        self.search(board, color, SearchLimits::depth(depth)).best_move
+    }
+
+    /// 固定深度的 MultiPV 搜尋，回傳分數最高的 `count` 條候選變例（檢查殘局題目是否唯一解用）
+    pub fn top_moves(&mut self, board: &Board, color: Color, depth: usize, count: usize) -> Vec<PvLine> {
+        let multi_pv = self.options.multi_pv;
+        self.options.multi_pv = count;
+        let result = self.search(board, color, SearchLimits::depth(depth));
+        self.options.multi_pv = multi_pv;
+        result.lines
+    }
+
+    /// 迭代加深搜尋：深度 1、2、3… 逐輪搜尋直到達到限制
+    /// 回傳最後一輪完整搜尋的結果；第一輪一定會完成，所以有合法走法時 `best_move` 不會是 None
+    pub fn search(&mut self, board: &Board, color: Color, limits: SearchLimits) -> SearchResult {
+        self.search_with(board, color, limits, |_| {})
+    }
+
+    /// 同 `search`，每完成一輪迭代就以該輪的結果呼叫 `on_iteration`（介面顯示思考過程用）
+    /// `limits.threads` 大於 1 時以 Lazy SMP 搜尋：輔助執行緒以不同的起始深度搜尋同一局面、
+    /// 透過共用的置換表互相加速，結果一律取自主執行緒，節點數則包含所有執行緒
+    pub fn search_with(
+        &mut self,
+        board: &Board,
+        color: Color,
+        limits: SearchLimits,
+        on_iteration: impl FnMut(&SearchResult),
+    ) -> SearchResult {
+        let start = Instant::now();
+        self.stop_signal.store(false, Ordering::Relaxed);
+        let max_depth = limits.depth.unwrap_or(MAX_DEPTH).max(1);
+
+        let (mut result, helper_nodes) = thread::scope(|scope| {
+            let helpers: Vec<_> = (1..limits.threads.max(1))
+                .map(|index| {
+                    let mut helper = self.helper();
+                    let mut work = board.clone();
+                    scope.spawn(move || helper.helper_search(&mut work, color, max_depth, index))
+                })
+                .collect();
+
+            let result = self.iterative_deepening(board, color, limits, start, on_iteration);
+            self.stop_signal.store(true, Ordering::Relaxed);
+            let helper_nodes: u64 = helpers.into_iter().map(|h| h.join().unwrap_or(0)).sum();
+            (result, helper_nodes)
+        });
+
+        result.nodes = self.nodes + helper_nodes;
+        result.elapsed = start.elapsed();
+        result.nps = (result.nodes as u128 * 1_000_000_000 / result.elapsed.as_nanos().max(1)) as u64;
+        result
+    }
+
+    /// 輔助執行緒：奇數編號從深一層開始，讓各執行緒的搜尋錯開，直到主執行緒發出停止訊號
+    /// 回傳走過的節點數
+    fn helper_search(&mut self, board: &mut Board, color: Color, max_depth: usize, index: usize) -> u64 {
+        self.ordering.clear();
+        let mut previous = None;
+        for depth in (1 + index % 2)..=max_depth {
+            previous = self.aspiration_search(board, color, depth, previous).map(|(_, score)| score);
+            if self.stopped {
+                break;
+            }
+        }
+        self.nodes
+    }
+
+    /// 主執行緒的迭代加深
+    fn iterative_deepening(
+        &mut self,
+        board: &Board,
+        color: Color,
+        limits: SearchLimits,
+        start: Instant,
+        mut on_iteration: impl FnMut(&SearchResult),
+    ) -> SearchResult {
+        self.limits = limits;
+        self.deadline = limits.time.map(|time| start + time);
+        self.nodes = 0;
+        self.can_stop = false;
+        self.stopped = false;
+        self.ordering.clear();
+
+        // 只複製一次棋盤，之後以 make/unmake 走子與還原
+        let mut work = board.clone();
+        let mut result = SearchResult::default();
+        for depth in 1..=limits.depth.unwrap_or(MAX_DEPTH).max(1) {
+            let previous = (depth > 1).then_some(result.score);
+            let Some((best_move, score)) = self.aspiration_search(&mut work, color, depth, previous) else {
+                if !self.stopped {
+                    // 無合法走法：分數即為將死或逼和
+                    result.score = terminal_score(&work, color, 0);
+                }
+                break;
+            };
+            let mut lines = vec![PvLine { best_move, score, pv: self.principal_variation(&mut work, color, depth) }];
+            // MultiPV：排除已找到的第一步，再搜尋下一條候選變例
+            while lines.len() < self.options.multi_pv && !self.stopped {
+                let excluded: Vec<Move> = lines.iter().map(|line| line.best_move).collect();
+                let Some((best_move, score)) = self.search_root(&mut work, color, depth, -INFINITY, INFINITY, &excluded)
+                else {
+                    break;
+                };
+                lines.push(PvLine { best_move, score, pv: self.principal_variation(&mut work, color, depth) });
+            }
+            if self.stopped {
+                break; // 這一輪沒搜完，沿用上一輪的結果
+            }
+            lines.sort_by_key(|line| std::cmp::Reverse(line.score));
+
+            let elapsed = start.elapsed();
+            let best = &lines[0];
+            result = SearchResult {
+                best_move: Some(best.best_move),
+                score: best.score,
+                pv: best.pv.clone(),
+                depth,
+                nodes: self.nodes,
+                nps: (self.nodes as u128 * 1_000_000_000 / elapsed.as_nanos().max(1)) as u64,
+                elapsed,
+                lines,
+            };
+            on_iteration(&result);
+            self.can_stop = true;
+            // 每條變例都已在這個深度內證明將死：更深的搜尋不會找到更短的將死
+            let mate_found = result.lines.iter().all(|line| {
+                line.score.abs() >= MATE_BOUND && (MATE_SCORE - line.score.abs()) as usize <= depth
+            });
+            if mate_found || self.limit_reached() {
+                break;
+            }
+        }
+        result
+    }
+
+    /// 上一次搜尋走過的節點數
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// 主要變例：先取三角形表記錄的走法，被置換表截斷時再沿著置換表的最佳走法補到 `depth` 步
+    fn principal_variation(&self, board: &mut Board, color: Color, depth: usize) -> Vec<Move> {
+        let mut pv = self.pv_table[0].clone();
+        let mut undos = Vec::with_capacity(depth);
+        let mut side = color;
+        for mv in &pv {
+            undos.push(board.make_move(mv));
+            side = side.opposite();
+        }
+
+        while pv.len() < depth {
+            let Some(hash_move) = self.tt.peek(position_hash(board, side)).and_then(|entry| entry.best_move) else {
+                break;
+            };
+            let Some(mv) = generate_legal_moves(board, side).into_iter().find(|m| same_move(m, &hash_move)) else {
+                break;
+            };
+            undos.push(board.make_move(&mv));
+            pv.push(mv);
+            side = side.opposite();
+        }
+
+        for (mv, undo) in pv.iter().zip(undos).rev() {
+            board.unmake_move(mv, undo);
+        }
+        pv
+    }
+
+    /// 清空第 `ply` 層的主要變例，並確保下一層也有位置
+    fn clear_pv(&mut self, ply: usize) {
+        if self.pv_table.len() < ply + 2 {
+            self.pv_table.resize(ply + 2, Vec::new());
+        }
+        self.pv_table[ply].clear();
+    }
+
+    /// 第 `ply` 層找到更好的走法：主要變例改為這一步接上下一層的主要變例
+    fn update_pv(&mut self, ply: usize, mv: Move) {
+        let (head, tail) = self.pv_table.split_at_mut(ply + 1);
+        let line = &mut head[ply];
+        line.clear();
+        line.push(mv);
+        line.extend_from_slice(&tail[0]);
+    }
+
+    /// 以渴望窗口搜尋根節點：分數落在窗口外就把該側窗口加倍重搜，直到分數落在窗口內
+    /// 沒有上一輪的分數、分數為將死或關閉此選項時直接以完整窗口搜尋
+    fn aspiration_search(
+        &mut self,
+        board: &mut Board,
+        color: Color,
+        depth: usize,
+        previous: Option<i32>,
+    ) -> Option<(Move, i32)> {
+        let Some(center) = previous.filter(|score| self.options.aspiration && score.abs() < MATE_BOUND) else {
+            return self.search_root(board, color, depth, -INFINITY, INFINITY, &[]);
+        };
+
+        let mut lower = self.options.aspiration_window.saturating_mul(self.pawn_value()).max(1);
+        let mut upper = lower;
+        loop {
+            let alpha = if lower >= MATE_BOUND { -INFINITY } else { center - lower };
+            let beta = if upper >= MATE_BOUND { INFINITY } else { center + upper };
+            let (mv, score) = self.search_root(board, color, depth, alpha, beta, &[])?;
+            if score <= alpha && alpha > -INFINITY {
+                lower *= 2;
+            } else if score >= beta && beta < INFINITY {
+                upper *= 2;
+            } else {
+                return Some((mv, score));
+            }
+        }
+    }
+
+    /// 在窗口 (`alpha`, `beta`) 內搜尋根節點一輪，回傳最佳走法與分數，`excluded` 中的走法不搜尋
+    /// 前一輪的最佳走法會因置換表排在最前面
+    fn search_root(
+        &mut self,
+        board: &mut Board,
+        color: Color,
+        depth: usize,
+        mut alpha: i32,
+        beta: i32,
+        excluded: &[Move],
+    ) -> Option<(Move, i32)> {
+        self.clear_pv(0);
+        let mut moves = generate_legal_moves(board, color);
+        moves.retain(|mv| !excluded.iter().any(|ex| same_move(ex, mv)));
+        let key = position_hash(board, color);
+        let hash_move = self.tt.probe(key).and_then(|entry| entry.best_move);
+        self.ordering.order(board, color, &mut moves, 0, hash_move.as_ref(), |kind| self.evaluator.piece_value(kind));
+
+        let original_alpha = alpha;
+        let mut best_score = -INFINITY;
+        let mut best_move = None;
+        for (index, mv) in moves.into_iter().enumerate() {
+            let undo = board.make_move(&mv);
+            let score = if index == 0 || !self.options.pvs {
+                -self.negamax(board, depth - 1, color.opposite(), -beta, -alpha, 1, Node::Normal)
             } else {
-                score < best_score
+                let score = -self.negamax(board, depth - 1, color.opposite(), -alpha - 1, -alpha, 1, Node::Normal);
+                if score > alpha && score < beta {
+                    -self.negamax(board, depth - 1, color.opposite(), -beta, -alpha, 1, Node::Normal)
+                } else {
+                    score
+                }
             };
+            board.unmake_move(&mv, undo);
+            if self.stopped {
+                return None;
+            }
 
-            if better {
+            if best_move.is_none() || score > best_score {
                 best_score = score;
-                best_move = Some((piece.pos, to));
+                best_move = Some(mv);
+                self.update_pv(0, mv);
+            }
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
             }
         }
+
+        let bound = if best_score <= original_alpha {
+            Bound::Upper
+        } else if best_score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        // 排除部分走法時的結果不是這個局面真正的最佳走法，不寫入置換表
+        if best_move.is_some() && excluded.is_empty() {
+            self.tt.store(key, depth, bound, best_score, best_move);
+        }
+        best_move.map(|mv| (mv, best_score))
     }
 
-    best_move
-}
+    /// 是否已達到節點數或時間限制，或被要求停止
+    fn limit_reached(&self) -> bool {
+        self.limits.nodes.is_some_and(|nodes| self.nodes >= nodes)
+            || self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+            || self.cancel.is_stopped()
+    }
 
-/// Minimax + Alpha-Beta 剪枝遞迴主體
-fn minimax_ab(board: &Board, depth: usize, current_color: Color, mut alpha: i32, mut beta: i32) -> i32 {
-    if depth == 0 {
-        return evaluate_board(board);
+    /// 搜尋中每個節點呼叫一次：達到限制後設定中止旗標，時間每隔一段節點才檢查
+    fn should_stop(&mut self) -> bool {
+        if self.stopped {
+            return true;
+        }
+        if !self.can_stop {
+            return false;
+        }
+        let out_of_nodes = self.limits.nodes.is_some_and(|nodes| self.nodes >= nodes);
+        let out_of_time = self.nodes.is_multiple_of(TIME_CHECK_INTERVAL)
+            && self.deadline.is_some_and(|deadline| Instant::now() >= deadline);
+        self.stopped = out_of_nodes
+            || out_of_time
+            || self.stop_signal.load(Ordering::Relaxed)
+            || self.cancel.is_stopped();
+        self.stopped
     }
 
-    let mut best_score = if current_color == Color::White { i32::MIN } else { i32::MAX };
+    /// 置換表的使用統計（探查次數、命中率等）
+    pub fn tt_stats(&self) -> TtStats {
+        self.tt.stats()
+    }
 
-    for piece in board.pieces.values() {
-        if piece.color != current_color {
-            continue;
+    /// Negamax + Alpha-Beta 剪枝遞迴主體，分數以輪到的一方為正
+    /// `node` 決定這個節點能否嘗試空著、是否讀寫置換表
+    /// 中止時回傳的分數沒有意義，呼叫端必須檢查 `stopped` 並捨棄
+    #[allow(clippy::too_many_arguments)]
+    fn negamax(
+        &mut self,
+        board: &mut Board,
+        depth: usize,
+        side: Color,
+        mut alpha: i32,
+        beta: i32,
+        ply: usize,
+        node: Node,
+    ) -> i32 {
+        self.clear_pv(ply);
+        if depth == 0 {
+            return self.quiescence(board, side, alpha, beta, ply, 0);
         }
 
-        let moves = get_legal_moves(board, piece);
-        for to in moves {
-            let mut new_board = board.clone();
-            new_board.remove_piece_at(to);
-            new_board.move_piece(piece.id, to);
-
-            let score = minimax_ab(&new_board, depth - 1, current_color.opposite(), alpha, beta);
+        self.nodes += 1;
+        if self.should_stop() {
+            return 0;
+        }
 
-            if current_color == Color::White {
-                best_score = best_score.max(score);
-                alpha = alpha.max(score);
-                if beta <= alpha {
-                    break; // beta 剪枝
+        let key = position_hash(board, side);
+        let use_tt = node != Node::Verify;
+        let mut hash_move = None;
+        if let Some(entry) = self.tt.probe(key).filter(|_| use_tt) {
+            hash_move = entry.best_move;
+            if entry.depth >= depth {
+                let score = score_from_tt(entry.score, ply);
+                match entry.bound {
+                    Bound::Exact => return score,
+                    Bound::Lower if score >= beta => return score,
+                    Bound::Upper if score <= alpha => return score,
+                    _ => {}
                 }
+            }
+        }
+
+        let in_check = is_in_check(board, side);
+        if node == Node::Normal && !in_check {
+            if let Some(score) = self.null_move_cutoff(board, depth, side, beta, ply) {
+                return score;
+            }
+        }
+
+        // 無合法走法：被將軍即為將死，否則為逼和
+        let mut moves = generate_legal_moves(board, side);
+        if moves.is_empty() {
+            return terminal_score(board, side, ply);
+        }
+        // 置換表走法、好的吃子與殺手走法先搜尋，較容易提早剪枝
+        self.ordering.order(board, side, &mut moves, ply, hash_move.as_ref(), |kind| self.evaluator.piece_value(kind));
+        let killers = self.ordering.killers(ply);
+
+        let original_alpha = alpha;
+        let mut best_score = -INFINITY;
+        let mut best_move = None;
+
+        for (index, mv) in moves.into_iter().enumerate() {
+            let undo = board.make_move(&mv);
+            let score = if index == 0 {
+                -self.negamax(board, depth - 1, side.opposite(), -beta, -alpha, ply + 1, Node::Normal)
             } else {
-                best_score = best_score.min(score);
-                beta = beta.min(score);
-                if beta <= alpha {
-                    break; // alpha 剪枝
+                // 後段的安靜走法先縮減深度試探，超過 alpha 才以完整深度重搜
+                let quiet = !mv.is_capture()
+                    && mv.promotion.is_none()
+                    && !in_check
+                    && !killers.iter().flatten().any(|killer| same_move(killer, &mv))
+                    && !is_in_check(board, side.opposite());
+                let reduction = if self.options.late_move_reductions
+                    && quiet
+                    && depth >= 3
+                    && index >= self.options.lmr_min_moves
+                {
+                    if index >= 2 * self.options.lmr_min_moves + 3 { 2 } else { 1 }
+                } else {
+                    0
+                };
+
+                // PVS：先以零寬窗口證明這一步不比目前最好的走法好
+                let (lower, upper) = if self.options.pvs { (-alpha - 1, -alpha) } else { (-beta, -alpha) };
+                let mut score = -self.negamax(board, depth - 1 - reduction, side.opposite(), lower, upper, ply + 1, Node::Normal);
+                if reduction > 0 && score > alpha {
+                    score = -self.negamax(board, depth - 1, side.opposite(), lower, upper, ply + 1, Node::Normal);
                 }
+                if self.options.pvs && score > alpha && score < beta {
+                    score = -self.negamax(board, depth - 1, side.opposite(), -beta, -alpha, ply + 1, Node::Normal);
+                }
+                score
+            };
+            board.unmake_move(&mv, undo);
+            if self.stopped {
+                return 0;
             }
+
+            if score > best_score {
+                best_score = score;
+                best_move = Some(mv);
+            }
+            if score > alpha {
+                alpha = score;
+                self.update_pv(ply, mv);
+            }
+            if alpha >= beta {
+                self.ordering.record_cutoff(side, &mv, ply, depth);
+                break; // beta 剪枝
+            }
+        }
+
+        let bound = if best_score <= original_alpha {
+            Bound::Upper
+        } else if best_score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        if use_tt {
+            self.tt.store(key, depth, bound, score_to_tt(best_score, ply), best_move);
         }
+
+        best_score
+    }
+
+    /// 空著剪枝：輪到的一方放棄走子，對方以減少的深度搜尋後仍不低於 beta，
+    /// 表示正常走子只會更好，可直接剪枝。回傳 Some(分數) 表示剪枝
+    fn null_move_cutoff(&mut self, board: &mut Board, depth: usize, side: Color, beta: i32, ply: usize) -> Option<i32> {
+        let reduction = self.options.null_move_reduction;
+        // 只剩王與兵時容易出現「不得不走壞棋」的局面，空著的假設不成立
+        if !self.options.null_move
+            || ply == 0
+            || depth <= reduction
+            || beta.abs() >= MATE_BOUND
+            || !has_non_pawn_material(board, side)
+            || side_sign(side) * self.evaluator.evaluate(board) < beta
+        {
+            return None;
+        }
+
+        let undo = board.make_null_move();
+        let score = -self.negamax(board, depth - 1 - reduction, side.opposite(), -beta, -beta + 1, ply + 1, Node::AfterNull);
+        board.unmake_null_move(undo);
+        if self.stopped || score < beta {
+            return None;
+        }
+
+        // 驗證：不使用空著、以減少的深度正常搜尋一次，同樣不低於 beta 才剪枝
+        // 驗證與本節點是同一局面，不讀寫置換表，以免讀到自己剛存的結果
+        if self.options.null_move_verification {
+            let verified = self.negamax(board, depth - reduction, side, beta - 1, beta, ply, Node::Verify);
+            if self.stopped || verified < beta {
+                return None;
+            }
+        }
+        // 空著搜尋找到的將死不可信（實際上不能不走），只回傳 beta
+        Some(if score >= MATE_BOUND { beta } else { score })
+    }
+
+    /// 靜態搜尋：深度用完後只繼續搜尋吃子，直到局面平靜或達到層數上限
+    /// 被將軍時沒有「不走」的選擇，所以改為搜尋所有解將走法
+    fn quiescence(&mut self, board: &mut Board, side: Color, mut alpha: i32, beta: i32, ply: usize, qply: usize) -> i32 {
+        self.nodes += 1;
+        if self.should_stop() {
+            return 0;
+        }
+
+        if !self.options.quiescence || qply >= self.options.quiescence_depth {
+            if !any_legal_move(board, side) {
+                return terminal_score(board, side, ply);
+            }
+            return side_sign(side) * self.evaluator.evaluate(board);
+        }
+
+        let mut moves = generate_legal_moves(board, side);
+        if moves.is_empty() {
+            return terminal_score(board, side, ply);
+        }
+        order_captures(board, &mut moves, |kind| self.evaluator.piece_value(kind));
+
+        // 不吃子也能保有目前的靜態評分（stand pat），已超過 beta 就不必再搜尋
+        let stand_pat = side_sign(side) * self.evaluator.evaluate(board);
+        let in_check = is_in_check(board, side);
+        let mut best_score = -INFINITY;
+        if !in_check {
+            if stand_pat >= beta {
+                return stand_pat;
+            }
+            best_score = stand_pat;
+            alpha = alpha.max(stand_pat);
+        }
+
+        for mv in moves {
+            if !in_check {
+                let capture = mv.is_capture();
+                let check = !capture && self.options.quiescence_checks && qply == 0 && gives_check(board, &mv, side);
+                if !capture && !check {
+                    continue;
+                }
+                // Delta 剪枝：就算吃到這個子也追不上 alpha
+                let margin = self.options.delta_margin.saturating_mul(self.pawn_value());
+                if capture && stand_pat + self.material_gain(&mv) + margin <= alpha {
+                    continue;
+                }
+            }
+
+            let undo = board.make_move(&mv);
+            let score = -self.quiescence(board, side.opposite(), -beta, -alpha, ply + 1, qply + 1);
+            board.unmake_move(&mv, undo);
+            if self.stopped {
+                return 0;
+            }
+
+            best_score = best_score.max(score);
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best_score
     }
 
-    best_score
+    /// 評估器中兵的分數，作為以兵為單位的選項的換算單位（至少為 1）
+    fn pawn_value(&self) -> i32 {
+        self.evaluator.piece_value(PieceType::Pawn).max(1)
+    }
+
+    /// 走法得到的物料（以評估器的分數計）：吃掉的棋子加上吃子升變多出的價值
+    fn material_gain(&self, mv: &Move) -> i32 {
+        let value = |kind| self.evaluator.piece_value(kind);
+        let captured = mv.captured.map_or(0, |p| value(p.kind));
+        let promoted = mv.promotion.map_or(0, |kind| value(kind) - value(PieceType::Pawn));
+        captured + promoted
+    }
 }
 
-/// 顏色互換工具
-trait OppositeColor {
-    fn opposite(self) -> Self;
+/// `side` 是否還有王與兵以外的棋子
+fn has_non_pawn_material(board: &Board, side: Color) -> bool {
+    board
+        .pieces
+        .values()
+        .any(|p| p.color == side && !matches!(p.kind, PieceType::King | PieceType::Pawn))
 }
 
-impl OppositeColor for Color {
-    fn opposite(self) -> Self {
-        match self {
-            Color::White => Color::Black,
-            Color::Black => Color::White,
-        }
+/// 走了 `mv` 之後是否將軍對方
+fn gives_check(board: &mut Board, mv: &Move, side: Color) -> bool {
+    let undo = board.make_move(mv);
+    let check = is_in_check(board, side.opposite());
+    board.unmake_move(mv, undo);
+    check
+}
+
+/// 分數文字，見 `SearchResult::score_string`
+fn score_string(score: i32) -> String {
+    match mate_in(score) {
+        Some(moves) => format!("#{}", moves),
+        None => score.to_string(),
+    }
+}
+
+/// 以代數記譜列出走法序列
+fn pv_notation(pv: &[Move], height: usize) -> String {
+    pv.iter().map(|mv| mv.notation(height)).collect::<Vec<_>>().join(" ")
+}
+
+/// 白方為 1、黑方為 -1，把白方觀點的評分轉成輪到的一方的觀點
+fn side_sign(side: Color) -> i32 {
+    match side {
+        Color::White => 1,
+        Color::Black => -1,
+    }
+}
+
+/// 無子可動時的分數（輪到的一方觀點）：在第 `ply` 層被將死為 `-(MATE_SCORE - ply)`，逼和為 0
+fn terminal_score(board: &Board, side_to_move: Color, ply: usize) -> i32 {
+    if is_in_check(board, side_to_move) {
+        -(MATE_SCORE - ply as i32)
+    } else {
+        0
+    }
+}
+
+/// 將死分數轉成「從目前局面起算」再存入置換表，同一局面在不同層數取出時才能換算正確的步數
+fn score_to_tt(score: i32, ply: usize) -> i32 {
+    if score >= MATE_BOUND {
+        score + ply as i32
+    } else if score <= -MATE_BOUND {
+        score - ply as i32
+    } else {
+        score
+    }
+}
+
+/// `score_to_tt` 的反向換算
+fn score_from_tt(score: i32, ply: usize) -> i32 {
+    if score >= MATE_BOUND {
+        score - ply as i32
+    } else if score <= -MATE_BOUND {
+        score + ply as i32
+    } else {
+        score
+    }
+}
+
+/// 分數對應的將死步數（見 `SearchResult::mate_in`），不是將死分數則為 None
+pub fn mate_in(score: i32) -> Option<i32> {
+    if score.abs() < MATE_BOUND {
+        return None;
+    }
+    let plies = MATE_SCORE - score.abs();
+    if score > 0 {
+        Some((plies + 1) / 2)
+    } else {
+        Some(-(plies / 2))
     }
 }
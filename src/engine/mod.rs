@@ -1,9 +1,20 @@
+#[cfg(feature = "bitboard")]
+pub mod bitboard;
 pub mod board;
+pub mod castling;
 pub mod piece;
 pub mod types;
 pub mod movement;
+pub mod moves;
+pub mod ordering;
 pub mod utils;
 pub mod evaluator;
+pub mod eval_params;
 pub mod generator;
 pub mod game;
-pub mod minmax;
\ No newline at end of file
+pub mod mate;
+pub mod minmax;
+pub mod pst;
+pub mod skill;
+pub mod tt;
+pub mod zobrist;
\ No newline at end of file
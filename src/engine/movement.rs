@@ -1,9 +1,195 @@
 use crate::engine::board::Board;
+use crate::engine::moves::{Move, MoveKind};
 use crate::engine::piece::Piece;
 use crate::engine::types::{Color, PieceType, Position};
 
-/// 取得特定棋子的合法走法
+/// 取得特定棋子的合法目標格（已排除會讓己方國王被將軍的走法，國王包含易位目標格）
 pub fn get_legal_moves(board: &Board, piece: &Piece) -> Vec<Position> {
+    let mut targets = Vec::new();
+    for mv in get_legal_move_list(board, piece) {
+        if !targets.contains(&mv.to) {
+            targets.push(mv.to); // 升變的多種選擇只列一次
+        }
+    }
+    targets
+}
+
+/// 兵升變時可選擇的棋種
+pub const PROMOTION_PIECES: [PieceType; 4] = [
+    PieceType::Queen,
+    PieceType::Rook,
+    PieceType::Bishop,
+    PieceType::Knight,
+];
+
+/// 取得特定棋子的合法走法，兵走到底線時展開為每一種升變選擇
+pub fn get_legal_move_list(board: &Board, piece: &Piece) -> Vec<Move> {
+    let mut work = board.clone();
+    legal_moves_of(&mut work, piece)
+}
+
+/// 取得某一方所有合法走法
+pub fn get_all_legal_moves(board: &Board, color: Color) -> Vec<Move> {
+    let mut work = board.clone();
+    generate_legal_moves(&mut work, color)
+}
+
+/// 產生某一方所有合法走法，以 make/unmake 檢查國王安全，結束後棋盤回到原狀（搜尋用，不複製棋盤）
+pub fn generate_legal_moves(board: &mut Board, color: Color) -> Vec<Move> {
+    let pieces: Vec<Piece> = board.pieces.values()
+        .filter(|p| p.color == color)
+        .copied()
+        .collect();
+    let mut moves = Vec::new();
+    for piece in &pieces {
+        moves.extend(legal_moves_of(board, piece));
+    }
+    moves
+}
+
+/// 某一方是否還有任何合法走法（找到第一步就停止，結束後棋盤回到原狀）
+pub fn any_legal_move(board: &mut Board, color: Color) -> bool {
+    let pieces: Vec<Piece> = board.pieces.values()
+        .filter(|p| p.color == color)
+        .copied()
+        .collect();
+    for piece in &pieces {
+        for mv in pseudo_move_list(board, piece) {
+            if keeps_king_safe(board, &mv, color) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn legal_moves_of(board: &mut Board, piece: &Piece) -> Vec<Move> {
+    let mut moves: Vec<Move> = pseudo_move_list(board, piece)
+        .into_iter()
+        .filter(|mv| keeps_king_safe(board, mv, piece.color))
+        .collect();
+    if piece.kind == PieceType::King {
+        for to in castling_moves(board, piece) {
+            moves.push(Move::castle(piece.pos, to));
+        }
+    }
+    moves
+}
+
+/// 將偽合法目標格轉成完整走法：記錄被吃棋子，標記兵前進兩格、吃過路兵，並展開升變選擇
+fn pseudo_move_list(board: &Board, piece: &Piece) -> Vec<Move> {
+    let mut moves = Vec::new();
+    for to in get_pseudo_legal_moves(board, piece) {
+        let mut mv = Move::new(piece.pos, to);
+        mv.captured = board.get_piece_at(to).copied();
+        if piece.kind == PieceType::Pawn {
+            if piece.pos.1.abs_diff(to.1) == 2 {
+                mv.kind = MoveKind::DoubleStep;
+            } else if piece.pos.0 != to.0 && mv.captured.is_none() {
+                mv.kind = MoveKind::EnPassant;
+                mv.captured = board.get_piece_at((to.0, piece.pos.1)).copied();
+            }
+            if is_promotion(board, piece, to) {
+                for kind in PROMOTION_PIECES {
+                    moves.push(Move { promotion: Some(kind), ..mv });
+                }
+                continue;
+            }
+        }
+        moves.push(mv);
+    }
+    moves
+}
+
+/// 模擬走子後，己方國王是否仍安全（走完立即還原）
+fn keeps_king_safe(board: &mut Board, mv: &Move, color: Color) -> bool {
+    let undo = board.make_move(mv);
+    let safe = !is_in_check(board, color);
+    board.unmake_move(mv, undo);
+    safe
+}
+
+/// 走到 `to` 是否為兵升變（白兵往 y = 0、黑兵往 y = height - 1 前進）
+pub fn is_promotion(board: &Board, piece: &Piece, to: Position) -> bool {
+    if piece.kind != PieceType::Pawn {
+        return false;
+    }
+    match piece.color {
+        Color::White => to.1 == 0,
+        Color::Black => to.1 + 1 == board.height,
+    }
+}
+
+/// 國王可易位的目標格：
+/// 該側仍有易位權利、車仍在原位、王與車之間全是空格（禁區也會擋住），
+/// 且王目前未被將軍，跨過與抵達的格子都不受攻擊
+fn castling_moves(board: &Board, king: &Piece) -> Vec<Position> {
+    let mut moves = Vec::new();
+    if !board.castling.any() || is_in_check(board, king.color) {
+        return moves;
+    }
+    let (kx, y) = king.pos;
+    for king_side in [true, false] {
+        let rook_pos = match board.castling.get(king.color, king_side) {
+            Some(pos) if pos.1 == y && pos.0.abs_diff(kx) >= 3 => pos,
+            _ => continue,
+        };
+        let rook_ok = board.get_piece_at(rook_pos)
+            .map(|p| p.kind == PieceType::Rook && p.color == king.color)
+            .unwrap_or(false);
+        if !rook_ok {
+            continue;
+        }
+        let between = if king_side { kx + 1..rook_pos.0 } else { rook_pos.0 + 1..kx };
+        if !between.clone().all(|x| board.is_tile_free((x, y))) {
+            continue;
+        }
+        let (step, dest) = if king_side { (kx + 1, kx + 2) } else { (kx - 1, kx - 2) };
+        let enemy = king.color.opposite();
+        if is_square_attacked(board, (step, y), enemy) || is_square_attacked(board, (dest, y), enemy) {
+            continue;
+        }
+        moves.push((dest, y));
+    }
+    moves
+}
+
+/// 某一方是否還有任何合法走法
+pub fn has_legal_moves(board: &Board, color: Color) -> bool {
+    let mut work = board.clone();
+    any_legal_move(&mut work, color)
+}
+
+/// 判斷某方國王是否正被將軍（沒有國王則視為未被將軍）
+pub fn is_in_check(board: &Board, color: Color) -> bool {
+    let king_pos = board.pieces.values()
+        .find(|p| p.kind == PieceType::King && p.color == color)
+        .map(|p| p.pos);
+    match king_pos {
+        Some(pos) => is_square_attacked(board, pos, color.opposite()),
+        None => false,
+    }
+}
+
+/// 判斷某格是否受到 `by` 方任一棋子攻擊
+pub fn is_square_attacked(board: &Board, pos: Position, by: Color) -> bool {
+    board.pieces.values()
+        .filter(|p| p.color == by)
+        .any(|p| attacks_square(board, p, pos))
+}
+
+/// 取得特定棋子的偽合法走法（只看棋子走法，不檢查國王安全）
+/// 開啟 `bitboard` feature 時，16x16 以內的棋盤改用位元棋盤產生器
+pub fn get_pseudo_legal_moves(board: &Board, piece: &Piece) -> Vec<Position> {
+    #[cfg(feature = "bitboard")]
+    if let Some(bits) = board.bits() {
+        return crate::engine::bitboard::pseudo_legal_moves(board, bits, piece);
+    }
+    array_pseudo_legal_moves(board, piece)
+}
+
+/// 逐格掃描棋盤陣列的偽合法走法產生器（預設後端）
+pub fn array_pseudo_legal_moves(board: &Board, piece: &Piece) -> Vec<Position> {
     match piece.kind {
         PieceType::Knight => knight_moves(board, piece),
         PieceType::Rook => rook_moves(board, piece),
@@ -69,7 +255,6 @@ fn king_moves(board: &Board, piece: &Piece) -> Vec<Position> {
             if nx >= 0 && ny >= 0 {
                 let pos = (nx as usize, ny as usize);
                 if board.is_tile_free(pos) || board.get_piece_at(pos).map(|p| p.color != piece.color).unwrap_or(false) {
-                    // TODO: 加入檢查是否會被將軍
                     Some(pos)
                 } else {
                     None
@@ -94,8 +279,21 @@ fn pawn_moves(board: &Board, piece: &Piece) -> Vec<Position> {
         let forward_pos = (x, ny as usize);
         if board.is_tile_free(forward_pos) {
             moves.push(forward_pos);
+
+            // 起始列可前進兩格（兩格都必須是空的，禁區也會擋住）
+            let start_rank = match piece.color {
+                Color::White => board.height.wrapping_sub(2),
+                Color::Black => 1,
+            };
+            let ny2 = ny + direction;
+            if board.pawn_double_step && y == start_rank && ny2 >= 0 {
+                let double_pos = (x, ny2 as usize);
+                if board.is_tile_free(double_pos) {
+                    moves.push(double_pos);
+                }
+            }
         }
-        // 吃子：左斜與右斜
+        // 吃子：左斜與右斜（含吃過路兵）
         for dx in [-1, 1] {
             let nx = x as isize + dx;
             if nx >= 0 && nx < board.width as isize {
@@ -104,6 +302,8 @@ fn pawn_moves(board: &Board, piece: &Piece) -> Vec<Position> {
                     if target.color != piece.color {
                         moves.push(pos);
                     }
+                } else if board.en_passant == Some(pos) && is_en_passant_victim(board, piece, (pos.0, y)) {
+                    moves.push(pos);
                 }
             }
         }
@@ -111,6 +311,13 @@ fn pawn_moves(board: &Board, piece: &Piece) -> Vec<Position> {
     moves
 }
 
+/// 過路兵目標格旁邊是否為可被吃的敵方兵
+fn is_en_passant_victim(board: &Board, pawn: &Piece, pos: Position) -> bool {
+    board.get_piece_at(pos)
+        .map(|p| p.kind == PieceType::Pawn && p.color != pawn.color)
+        .unwrap_or(false)
+}
+
 fn straight_line_moves(board: &Board, piece: &Piece, directions: &[(isize, isize)]) -> Vec<Position> {
     let mut moves = vec![];
     for &(dx, dy) in directions {
@@ -136,3 +343,17 @@ fn straight_line_moves(board: &Board, piece: &Piece, directions: &[(isize, isize
     }
     moves
 }
+
+/// 棋子是否攻擊某格（兵只斜向攻擊，其餘棋子與走法相同）
+fn attacks_square(board: &Board, piece: &Piece, target: Position) -> bool {
+    if piece.kind == PieceType::Pawn {
+        let direction: isize = match piece.color {
+            Color::White => -1,
+            Color::Black => 1,
+        };
+        let (x, y) = (piece.pos.0 as isize, piece.pos.1 as isize);
+        let (tx, ty) = (target.0 as isize, target.1 as isize);
+        return ty == y + direction && (tx - x).abs() == 1;
+    }
+    get_pseudo_legal_moves(board, piece).contains(&target)
+}
@@ -0,0 +1,84 @@
+use super::castling::CastlingRights;
+use super::piece::Piece;
+use super::types::{PieceType, Position};
+
+/// 走法種類（需要額外處理的特殊走法）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveKind {
+    Normal,
+    /// 兵從起始列前進兩格，會留下過路兵目標格
+    DoubleStep,
+    /// 吃過路兵：被吃的兵不在 `to`，而在 `captured.pos`
+    EnPassant,
+    /// 王車易位：`from`/`to` 為王的起終點，車由棋盤的易位權利決定
+    Castle,
+}
+
+/// 一步棋：起點、終點、被吃的棋子、兵升變時選擇的棋種，以及特殊走法標記
+/// 由走法產生器建立時會填好 `captured` 與 `kind`；`Board::make_move` 依 `kind` 執行，
+/// 實際吃掉的棋子則由棋盤判斷，因此手動建立的吃子走法也能正確執行與還原
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub from: Position,
+    pub to: Position,
+    pub captured: Option<Piece>,
+    pub promotion: Option<PieceType>,
+    pub kind: MoveKind,
+}
+
+impl Move {
+    /// 一般走法（無升變、不吃子）
+    pub fn new(from: Position, to: Position) -> Self {
+        Move { from, to, captured: None, promotion: None, kind: MoveKind::Normal }
+    }
+
+    /// 兵升變走法
+    pub fn with_promotion(from: Position, to: Position, kind: PieceType) -> Self {
+        Move { promotion: Some(kind), ..Move::new(from, to) }
+    }
+
+    /// 王車易位（王從 `from` 走兩格到 `to`）
+    pub fn castle(from: Position, to: Position) -> Self {
+        Move { kind: MoveKind::Castle, ..Move::new(from, to) }
+    }
+
+    /// 是否吃子
+    pub fn is_capture(&self) -> bool {
+        self.captured.is_some()
+    }
+
+    /// 代數記譜，例如 e2e4、e7e8q，列數需要棋盤高度換算
+    pub fn notation(&self, height: usize) -> String {
+        let promotion = match self.promotion {
+            Some(PieceType::Queen) => "q",
+            Some(PieceType::Rook) => "r",
+            Some(PieceType::Bishop) => "b",
+            Some(PieceType::Knight) => "n",
+            Some(_) => "?",
+            None => "",
+        };
+        format!("{}{}{}", square_name(self.from, height), square_name(self.to, height), promotion)
+    }
+}
+
+/// 格子名稱：直行依序為 a、b、c…，橫列從白方底線算起為 1、2、3…
+/// 例如 8x8 棋盤的 (4, 6) 為 e2；超過 26 行的棋盤以 `[x]` 表示直行
+pub fn square_name(pos: Position, height: usize) -> String {
+    let rank = height - pos.1;
+    if pos.0 < 26 {
+        format!("{}{}", (b'a' + pos.0 as u8) as char, rank)
+    } else {
+        format!("[{}]{}", pos.0, rank)
+    }
+}
+
+/// `Board::make_move` 回傳的還原資訊：走子前無法從走法本身推回的棋盤狀態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UndoInfo {
+    pub en_passant: Option<Position>,
+    pub castling: CastlingRights,
+    /// 實際被吃掉的棋子（吃過路兵時為 `to` 旁邊的兵）
+    pub captured: Option<Piece>,
+    /// 走動的棋子 ID；起點沒有棋子時為 None，這一步不會改動棋盤
+    pub moved: Option<u32>,
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use super::board::Board;
+use super::moves::Move;
+use super::types::{Color, PieceType, Position};
+
+// 排序分數的區段：置換表走法 > 吃子與升變 > 殺手走法 > 歷史分數
+const HASH_MOVE_SCORE: i32 = 1_000_000;
+const CAPTURE_SCORE: i32 = 100_000;
+const FIRST_KILLER_SCORE: i32 = 90_000;
+const SECOND_KILLER_SCORE: i32 = 80_000;
+/// 歷史分數的上限，超過時全部減半，確保不會蓋過殺手走法
+const HISTORY_MAX: i32 = 50_000;
+
+/// 是否為同一步棋（只比較起終點與升變，不比較被吃棋子的編號）
+pub fn same_move(a: &Move, b: &Move) -> bool {
+    a.from == b.from && a.to == b.to && a.promotion == b.promotion
+}
+
+/// MVV-LVA：先吃價值最高的棋子，同樣的目標則用價值最低的棋子去吃
+/// 吃子升變再加上升變得到的價值；棋子價值由 `value` 提供（通常是評估器的 `piece_value`）
+pub fn mvv_lva(board: &Board, mv: &Move, value: impl Fn(PieceType) -> i32) -> i32 {
+    let victim = mv.captured.map_or(0, |p| value(p.kind));
+    let attacker = board.get_piece_at(mv.from).map_or(0, |p| value(p.kind));
+    let promotion = mv.promotion.map_or(0, &value);
+    victim * 16 - attacker + promotion * 16
+}
+
+/// 走法排序的狀態：每層的殺手走法與雙方的歷史分數
+/// 殺手走法是在同一層造成 beta 剪枝的不吃子走法，歷史分數則累計各起終點造成剪枝的次數（依深度加權）
+#[derive(Debug, Clone, Default)]
+pub struct MoveOrdering {
+    killers: Vec<[Option<Move>; 2]>,
+    history: HashMap<(Color, Position, Position), i32>,
+}
+
+impl MoveOrdering {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 新的搜尋開始時清除殺手走法與歷史分數
+    pub fn clear(&mut self) {
+        self.killers.clear();
+        self.history.clear();
+    }
+
+    /// 第 `ply` 層的殺手走法
+    pub fn killers(&self, ply: usize) -> [Option<Move>; 2] {
+        self.killers.get(ply).copied().unwrap_or([None, None])
+    }
+
+    /// 走法的歷史分數
+    pub fn history(&self, side: Color, mv: &Move) -> i32 {
+        self.history.get(&(side, mv.from, mv.to)).copied().unwrap_or(0)
+    }
+
+    /// 記錄造成 beta 剪枝的走法：不吃子的走法才會成為殺手走法並累加歷史分數
+    pub fn record_cutoff(&mut self, side: Color, mv: &Move, ply: usize, depth: usize) {
+        if mv.is_capture() || mv.promotion.is_some() {
+            return;
+        }
+
+        if self.killers.len() <= ply {
+            self.killers.resize(ply + 1, [None, None]);
+        }
+        let slot = &mut self.killers[ply];
+        if !slot[0].is_some_and(|k| same_move(&k, mv)) {
+            slot[1] = slot[0];
+            slot[0] = Some(*mv);
+        }
+
+        let entry = self.history.entry((side, mv.from, mv.to)).or_insert(0);
+        *entry += (depth * depth) as i32;
+        if *entry > HISTORY_MAX {
+            for value in self.history.values_mut() {
+                *value /= 2;
+            }
+        }
+    }
+
+    /// 排序分數：置換表走法、吃子（MVV-LVA）與升變、殺手走法、歷史分數
+    /// MVV-LVA 限制在吃子的區段內，棋子價值再大也不會超過置換表走法
+    pub fn score(
+        &self,
+        board: &Board,
+        side: Color,
+        mv: &Move,
+        ply: usize,
+        hash_move: Option<&Move>,
+        value: impl Fn(PieceType) -> i32,
+    ) -> i32 {
+        if hash_move.is_some_and(|hm| same_move(hm, mv)) {
+            return HASH_MOVE_SCORE;
+        }
+        if mv.is_capture() || mv.promotion.is_some() {
+            return CAPTURE_SCORE + mvv_lva(board, mv, value).clamp(0, HASH_MOVE_SCORE - CAPTURE_SCORE - 1);
+        }
+        let [first, second] = self.killers(ply);
+        if first.is_some_and(|k| same_move(&k, mv)) {
+            return FIRST_KILLER_SCORE;
+        }
+        if second.is_some_and(|k| same_move(&k, mv)) {
+            return SECOND_KILLER_SCORE;
+        }
+        self.history(side, mv)
+    }
+
+    /// 依排序分數由高到低排列走法
+    /// 分數相同時依起點、終點與升變排序，讓結果只取決於局面而不受棋子存放順序影響
+    pub fn order(
+        &self,
+        board: &Board,
+        side: Color,
+        moves: &mut [Move],
+        ply: usize,
+        hash_move: Option<&Move>,
+        value: impl Fn(PieceType) -> i32,
+    ) {
+        moves.sort_by_cached_key(|mv| {
+            let score = self.score(board, side, mv, ply, hash_move, &value);
+            (-score, tie_break(mv, &value))
+        });
+    }
+}
+
+/// 只依吃子價值排序（靜態搜尋用），同分時同樣依起終點排序
+pub fn order_captures(board: &Board, moves: &mut [Move], value: impl Fn(PieceType) -> i32) {
+    moves.sort_by_cached_key(|mv| (-mvv_lva(board, mv, &value), tie_break(mv, &value)));
+}
+
+fn tie_break(mv: &Move, value: impl Fn(PieceType) -> i32) -> (Position, Position, i32) {
+    (mv.from, mv.to, mv.promotion.map_or(0, value))
+}
@@ -1,6 +1,6 @@
 use super::types::{Color, PieceType, Position};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Piece {
     pub id: u32,  // 唯一編號
     pub kind: PieceType,
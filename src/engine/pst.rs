@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+
+use crate::engine::board::Board;
+use crate::engine::types::{Color, PieceType, Position};
+
+/// 開局階段的最大值：雙方標準兵力（各 2 騎士、2 主教、2 城堡、1 皇后）時為 24，兵力越少越接近殘局
+pub const PHASE_TOTAL: i32 = 24;
+
+/// 棋種對局面階段的貢獻（兵與王不算）
+fn phase_weight(kind: PieceType) -> i32 {
+    match kind {
+        PieceType::Knight | PieceType::Bishop => 1,
+        PieceType::Rook => 2,
+        PieceType::Queen => 4,
+        PieceType::King | PieceType::Pawn | PieceType::Custom(_) => 0,
+    }
+}
+
+/// 目前局面的階段：PHASE_TOTAL 為開局／中局，0 為純殘局；大棋盤兵力較多時以 PHASE_TOTAL 為上限
+pub fn game_phase(board: &Board) -> i32 {
+    board.pieces.values().map(|p| phase_weight(p.kind)).sum::<i32>().min(PHASE_TOTAL)
+}
+
+/// 以正規化座標定義的位置分數表（以白方視角，第 0 列為白兵升變的一端）
+/// 表的大小不必與棋盤相同，取值時依格子中心在表上的相對位置做雙線性內插
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SquareTable(pub Vec<Vec<i32>>);
+
+impl SquareTable {
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|row| row.is_empty())
+    }
+
+    /// 在 `width` x `height` 的棋盤上，`color` 一方位於 `pos` 的分數（未取整）
+    /// 黑方上下鏡射，空表一律為 0
+    pub fn sample(&self, pos: Position, color: Color, width: usize, height: usize) -> f32 {
+        let rows = self.0.len();
+        let cols = self.0.iter().map(|row| row.len()).min().unwrap_or(0);
+        if rows == 0 || cols == 0 {
+            return 0.0;
+        }
+
+        let y = match color {
+            Color::White => pos.1,
+            Color::Black => height - 1 - pos.1,
+        };
+        // 格子中心的正規化座標換算成表上的連續座標（表格中心在整數點上）
+        let fx = (((pos.0 as f32 + 0.5) / width as f32) * cols as f32 - 0.5).clamp(0.0, (cols - 1) as f32);
+        let fy = (((y as f32 + 0.5) / height as f32) * rows as f32 - 0.5).clamp(0.0, (rows - 1) as f32);
+
+        let (x0, y0) = (fx.floor() as usize, fy.floor() as usize);
+        let (x1, y1) = ((x0 + 1).min(cols - 1), (y0 + 1).min(rows - 1));
+        let (tx, ty) = (fx - x0 as f32, fy - y0 as f32);
+        let at = |x: usize, y: usize| self.0[y][x] as f32;
+
+        let top = at(x0, y0) * (1.0 - tx) + at(x1, y0) * tx;
+        let bottom = at(x0, y1) * (1.0 - tx) + at(x1, y1) * tx;
+        top * (1.0 - ty) + bottom * ty
+    }
+}
+
+/// 一個棋種的中局表與殘局表，依局面階段線性漸變
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhaseTables {
+    pub middlegame: SquareTable,
+    /// 省略時與中局表相同
+    pub endgame: SquareTable,
+}
+
+impl PhaseTables {
+    fn new(middlegame: [[i32; 8]; 8], endgame: [[i32; 8]; 8]) -> Self {
+        let table = |rows: [[i32; 8]; 8]| SquareTable(rows.iter().map(|row| row.to_vec()).collect());
+        PhaseTables { middlegame: table(middlegame), endgame: table(endgame) }
+    }
+
+    /// 依 `phase`（0 ~ PHASE_TOTAL）在中局與殘局之間內插
+    pub fn sample(&self, pos: Position, color: Color, width: usize, height: usize, phase: i32) -> f32 {
+        let middlegame = self.middlegame.sample(pos, color, width, height);
+        if self.endgame.is_empty() {
+            return middlegame;
+        }
+        let endgame = self.endgame.sample(pos, color, width, height);
+        let phase = phase.clamp(0, PHASE_TOTAL) as f32;
+        (middlegame * phase + endgame * (PHASE_TOTAL as f32 - phase)) / PHASE_TOTAL as f32
+    }
+}
+
+/// 各棋種的位置分數表（單位與物料相同，以兵為 1）；自訂棋種不計位置分數
+/// 預設表每格最多一個兵，位置分數不會蓋過物料的差距
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PieceSquareTables {
+    /// 位置分數的倍數，0 表示不計位置
+    pub weight: i32,
+    pub pawn: PhaseTables,
+    pub knight: PhaseTables,
+    pub bishop: PhaseTables,
+    pub rook: PhaseTables,
+    pub queen: PhaseTables,
+    pub king: PhaseTables,
+}
+
+impl PieceSquareTables {
+    pub fn tables(&self, kind: PieceType) -> Option<&PhaseTables> {
+        match kind {
+            PieceType::Pawn => Some(&self.pawn),
+            PieceType::Knight => Some(&self.knight),
+            PieceType::Bishop => Some(&self.bishop),
+            PieceType::Rook => Some(&self.rook),
+            PieceType::Queen => Some(&self.queen),
+            PieceType::King => Some(&self.king),
+            PieceType::Custom(_) => None,
+        }
+    }
+
+    /// 棋子在 `board` 上的位置分數（以該棋子自己為正），`phase` 由 `game_phase` 取得
+    pub fn value(&self, board: &Board, kind: PieceType, color: Color, pos: Position, phase: i32) -> i32 {
+        let Some(tables) = self.tables(kind) else { return 0 };
+        let score = tables.sample(pos, color, board.width, board.height, phase);
+        (score * self.weight as f32).round() as i32
+    }
+}
+
+impl Default for PieceSquareTables {
+    fn default() -> Self {
+        PieceSquareTables {
+            weight: 1,
+            // 兵：中局重視中央兵，殘局接近升變時加分
+            pawn: PhaseTables::new(
+                [
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [1, 1, 1, 1, 1, 1, 1, 1],
+                    [0, 0, 1, 1, 1, 1, 0, 0],
+                    [0, 0, 0, 1, 1, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, -1, -1, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                ],
+                [
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [1, 1, 1, 1, 1, 1, 1, 1],
+                    [1, 1, 1, 1, 1, 1, 1, 1],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                ],
+            ),
+            // 騎士：邊角的騎士控制的格子少
+            knight: PhaseTables::new(
+                [
+                    [-1, -1, -1, -1, -1, -1, -1, -1],
+                    [-1, 0, 0, 0, 0, 0, 0, -1],
+                    [-1, 0, 1, 1, 1, 1, 0, -1],
+                    [-1, 0, 1, 1, 1, 1, 0, -1],
+                    [-1, 0, 1, 1, 1, 1, 0, -1],
+                    [-1, 0, 1, 1, 1, 1, 0, -1],
+                    [-1, 0, 0, 0, 0, 0, 0, -1],
+                    [-1, -1, -1, -1, -1, -1, -1, -1],
+                ],
+                [
+                    [-1, -1, -1, -1, -1, -1, -1, -1],
+                    [-1, 0, 0, 0, 0, 0, 0, -1],
+                    [-1, 0, 1, 1, 1, 1, 0, -1],
+                    [-1, 0, 1, 1, 1, 1, 0, -1],
+                    [-1, 0, 1, 1, 1, 1, 0, -1],
+                    [-1, 0, 1, 1, 1, 1, 0, -1],
+                    [-1, 0, 0, 0, 0, 0, 0, -1],
+                    [-1, -1, -1, -1, -1, -1, -1, -1],
+                ],
+            ),
+            bishop: PhaseTables::new(
+                [
+                    [-1, 0, 0, 0, 0, 0, 0, -1],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 1, 1, 1, 1, 0, 0],
+                    [0, 1, 1, 1, 1, 1, 1, 0],
+                    [0, 1, 1, 1, 1, 1, 1, 0],
+                    [0, 0, 1, 1, 1, 1, 0, 0],
+                    [0, 1, 0, 0, 0, 0, 1, 0],
+                    [-1, 0, 0, 0, 0, 0, 0, -1],
+                ],
+                [
+                    [-1, 0, 0, 0, 0, 0, 0, -1],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 1, 1, 1, 1, 0, 0],
+                    [0, 0, 1, 1, 1, 1, 0, 0],
+                    [0, 0, 1, 1, 1, 1, 0, 0],
+                    [0, 0, 1, 1, 1, 1, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [-1, 0, 0, 0, 0, 0, 0, -1],
+                ],
+            ),
+            // 城堡：進到對方第二排最有威脅
+            rook: PhaseTables::new(
+                [
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [1, 1, 1, 1, 1, 1, 1, 1],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 1, 1, 0, 0, 0],
+                ],
+                [
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [1, 1, 1, 1, 1, 1, 1, 1],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                ],
+            ),
+            queen: PhaseTables::new(
+                [
+                    [-1, 0, 0, 0, 0, 0, 0, -1],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [-1, 0, 0, 0, 0, 0, 0, -1],
+                ],
+                [
+                    [-1, 0, 0, 0, 0, 0, 0, -1],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [0, 0, 1, 1, 1, 1, 0, 0],
+                    [0, 0, 1, 1, 1, 1, 0, 0],
+                    [0, 0, 1, 1, 1, 1, 0, 0],
+                    [0, 0, 1, 1, 1, 1, 0, 0],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [-1, 0, 0, 0, 0, 0, 0, -1],
+                ],
+            ),
+            // 王：中局躲在己方底線，殘局走到中央參與戰鬥
+            king: PhaseTables::new(
+                [
+                    [-1, -1, -1, -1, -1, -1, -1, -1],
+                    [-1, -1, -1, -1, -1, -1, -1, -1],
+                    [-1, -1, -1, -1, -1, -1, -1, -1],
+                    [-1, -1, -1, -1, -1, -1, -1, -1],
+                    [-1, -1, -1, -1, -1, -1, -1, -1],
+                    [-1, -1, -1, -1, -1, -1, -1, -1],
+                    [0, 0, 0, 0, 0, 0, 0, 0],
+                    [1, 1, 0, 0, 0, 0, 1, 1],
+                ],
+                [
+                    [-1, -1, -1, -1, -1, -1, -1, -1],
+                    [-1, 0, 0, 0, 0, 0, 0, -1],
+                    [-1, 0, 1, 1, 1, 1, 0, -1],
+                    [-1, 0, 1, 1, 1, 1, 0, -1],
+                    [-1, 0, 1, 1, 1, 1, 0, -1],
+                    [-1, 0, 1, 1, 1, 1, 0, -1],
+                    [-1, 0, 0, 0, 0, 0, 0, -1],
+                    [-1, -1, -1, -1, -1, -1, -1, -1],
+                ],
+            ),
+        }
+    }
+}
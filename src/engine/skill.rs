@@ -0,0 +1,132 @@
+use rand::Rng;
+
+use super::board::Board;
+use super::evaluator::Evaluator;
+use super::minmax::{PvLine, SearchLimits, SearchResult, Searcher};
+use super::types::{Color, PieceType};
+
+/// 電腦的棋力等級：越低的等級搜尋越淺，也越常在接近最佳的走法中隨機挑選或故意走出失誤
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SkillLevel {
+    Beginner,
+    Novice,
+    Intermediate,
+    Advanced,
+    #[default]
+    Master,
+}
+
+/// 棋力等級對應的搜尋設定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkillSettings {
+    /// 最大搜尋深度，None 表示不限制
+    pub depth: Option<usize>,
+    /// 最大搜尋節點數，None 表示不限制
+    pub nodes: Option<u64>,
+    /// 搜尋的候選走法數（MultiPV）
+    pub candidates: usize,
+    /// 與最佳走法分數相差在此範圍內的走法都可能被選中（以兵的分數為單位）
+    pub tolerance: i32,
+    /// 故意失誤的機率（0.0 ~ 1.0）
+    pub inaccuracy_chance: f64,
+    /// 失誤時容許的分數損失（以兵的分數為單位）
+    pub inaccuracy_margin: i32,
+}
+
+impl SkillLevel {
+    /// 由弱到強的所有等級
+    pub const ALL: [SkillLevel; 5] = [
+        SkillLevel::Beginner,
+        SkillLevel::Novice,
+        SkillLevel::Intermediate,
+        SkillLevel::Advanced,
+        SkillLevel::Master,
+    ];
+
+    /// 介面顯示的名稱
+    pub fn name(self) -> &'static str {
+        match self {
+            SkillLevel::Beginner => "入門",
+            SkillLevel::Novice => "初級",
+            SkillLevel::Intermediate => "中級",
+            SkillLevel::Advanced => "高級",
+            SkillLevel::Master => "大師",
+        }
+    }
+
+    /// 各等級的搜尋設定；大師不限制深度，但仍有節點上限，呼叫端沒有給時間限制時搜尋也會結束
+    pub fn settings(self) -> SkillSettings {
+        let (depth, nodes, candidates, tolerance, inaccuracy_chance, inaccuracy_margin) = match self {
+            SkillLevel::Beginner => (Some(1), Some(2_000), 6, 2, 0.3, 5),
+            SkillLevel::Novice => (Some(2), Some(10_000), 4, 1, 0.2, 3),
+            SkillLevel::Intermediate => (Some(3), Some(50_000), 3, 1, 0.1, 2),
+            SkillLevel::Advanced => (Some(5), Some(300_000), 2, 0, 0.05, 1),
+            SkillLevel::Master => (None, Some(5_000_000), 1, 0, 0.0, 0),
+        };
+        SkillSettings { depth, nodes, candidates, tolerance, inaccuracy_chance, inaccuracy_margin }
+    }
+}
+
+impl SkillSettings {
+    /// 把深度與節點數上限套用到 `limits`，取較嚴格的一方
+    pub fn apply(&self, limits: SearchLimits) -> SearchLimits {
+        SearchLimits {
+            depth: limits.depth.into_iter().chain(self.depth).min(),
+            nodes: limits.nodes.into_iter().chain(self.nodes).min(),
+            ..limits
+        }
+    }
+
+    /// 把以兵為單位的分數邊際換算成評估器的分數（`pawn` 為評估器中兵的分數）
+    pub fn scaled(self, pawn: i32) -> Self {
+        SkillSettings {
+            tolerance: self.tolerance.saturating_mul(pawn),
+            inaccuracy_margin: self.inaccuracy_margin.saturating_mul(pawn),
+            ..self
+        }
+    }
+}
+
+/// 從候選變例（分數由高到低）中依棋力設定挑一條：
+/// 一般在容許範圍內隨機挑選，偶爾放寬範圍故意失誤；除非每一步都會被將死，否則不選會被將死的走法
+/// `settings` 的邊際直接與變例分數比較，評估器的兵不是 1 分時需先以 `SkillSettings::scaled` 換算
+pub fn pick_line<'a>(lines: &'a [PvLine], settings: &SkillSettings, rng: &mut impl Rng) -> Option<&'a PvLine> {
+    let best = lines.first()?;
+    let margin = if rng.random_bool(settings.inaccuracy_chance.clamp(0.0, 1.0)) {
+        settings.inaccuracy_margin
+    } else {
+        settings.tolerance
+    };
+    let candidates: Vec<&PvLine> = lines
+        .iter()
+        .filter(|line| line.score >= best.score - margin)
+        .filter(|line| std::ptr::eq(*line, best) || line.mate_in().is_none_or(|n| n > 0))
+        .collect();
+    Some(candidates[rng.random_range(0..candidates.len())])
+}
+
+/// 以指定棋力搜尋：套用深度與節點數上限、搜尋多個候選走法，再以 `rng` 依 `pick_line` 選出要走的一步
+/// 回傳結果的 `best_move`、`score` 與 `pv` 為選中的變例，`lines` 保留所有候選
+pub fn skill_search<E: Evaluator>(
+    searcher: &mut Searcher<E>,
+    board: &Board,
+    color: Color,
+    level: SkillLevel,
+    limits: SearchLimits,
+    rng: &mut impl Rng,
+    on_iteration: impl FnMut(&SearchResult),
+) -> SearchResult {
+    let pawn = searcher.evaluator().piece_value(PieceType::Pawn).max(1);
+    let settings = level.settings().scaled(pawn);
+    let multi_pv = searcher.options.multi_pv;
+    searcher.options.multi_pv = settings.candidates;
+    let mut result = searcher.search_with(board, color, settings.apply(limits), on_iteration);
+    searcher.options.multi_pv = multi_pv;
+
+    if let Some(line) = pick_line(&result.lines, &settings, rng).cloned() {
+        result.best_move = Some(line.best_move);
+        result.score = line.score;
+        result.pv = line.pv;
+    }
+    result
+}
@@ -0,0 +1,148 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use super::moves::Move;
+
+/// 置換表分數的種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    /// 精確分數
+    Exact,
+    /// 下界：搜尋在 beta 剪枝，真實分數 >= score
+    Lower,
+    /// 上界：沒有走法超過 alpha，真實分數 <= score
+    Upper,
+}
+
+/// 置換表的一筆資料
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TtEntry {
+    pub key: u64,
+    pub depth: usize,
+    pub bound: Bound,
+    pub score: i32,
+    pub best_move: Option<Move>,
+}
+
+/// 置換表使用統計
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TtStats {
+    pub probes: u64,
+    pub hits: u64,
+    pub stores: u64,
+    /// 寫入時覆蓋了另一個局面的資料
+    pub overwrites: u64,
+}
+
+impl TtStats {
+    /// 命中率（0.0 ~ 1.0）
+    pub fn hit_rate(&self) -> f64 {
+        if self.probes == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.probes as f64
+        }
+    }
+}
+
+/// 固定大小的置換表：以局面雜湊值的低位元選擇槽位，每個槽位存一筆資料
+/// 每個槽位各有一把鎖、統計使用原子計數，多執行緒搜尋時可以共用同一張表
+pub struct TranspositionTable {
+    entries: Vec<Mutex<Option<TtEntry>>>,
+    mask: usize,
+    probes: AtomicU64,
+    hits: AtomicU64,
+    stores: AtomicU64,
+    overwrites: AtomicU64,
+}
+
+impl TranspositionTable {
+    /// 建立至少一格、最多 `entries` 格的置換表（向下取到 2 的次方）
+    pub fn new(entries: usize) -> Self {
+        let size = if entries <= 1 { 1 } else { 1 << (usize::BITS - 1 - entries.leading_zeros()) };
+        TranspositionTable {
+            entries: (0..size).map(|_| Mutex::new(None)).collect(),
+            mask: size - 1,
+            probes: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            stores: AtomicU64::new(0),
+            overwrites: AtomicU64::new(0),
+        }
+    }
+
+    /// 依記憶體大小（MB）建立置換表
+    pub fn with_megabytes(megabytes: usize) -> Self {
+        let entry_size = std::mem::size_of::<Mutex<Option<TtEntry>>>();
+        Self::new(megabytes * 1024 * 1024 / entry_size)
+    }
+
+    /// 槽位數量
+    pub fn capacity(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 取得槽位的鎖；其他執行緒 panic 時仍沿用資料，置換表內容只影響效率不影響正確性
+    fn lock(entry: &Mutex<Option<TtEntry>>) -> MutexGuard<'_, Option<TtEntry>> {
+        entry.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn slot(&self, key: u64) -> MutexGuard<'_, Option<TtEntry>> {
+        Self::lock(&self.entries[key as usize & self.mask])
+    }
+
+    /// 查詢局面，只有雜湊值完全相同才算命中
+    pub fn probe(&self, key: u64) -> Option<TtEntry> {
+        self.probes.fetch_add(1, Ordering::Relaxed);
+        let entry = self.peek(key);
+        if entry.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        }
+        entry
+    }
+
+    /// 查詢局面但不計入統計（讀取主要變例等非搜尋用途）
+    pub fn peek(&self, key: u64) -> Option<TtEntry> {
+        self.slot(key).filter(|e| e.key == key)
+    }
+
+    /// 寫入局面：同一局面只在搜尋深度不低於舊資料時覆蓋，不同局面則直接取代
+    pub fn store(&self, key: u64, depth: usize, bound: Bound, score: i32, best_move: Option<Move>) {
+        let mut slot = self.slot(key);
+        if let Some(old) = *slot {
+            if old.key == key && old.depth > depth {
+                return;
+            }
+            if old.key != key {
+                self.overwrites.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        *slot = Some(TtEntry { key, depth, bound, score, best_move });
+        self.stores.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 清空所有資料與統計
+    pub fn clear(&self) {
+        for entry in &self.entries {
+            *Self::lock(entry) = None;
+        }
+        for counter in [&self.probes, &self.hits, &self.stores, &self.overwrites] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// 目前的使用統計
+    pub fn stats(&self) -> TtStats {
+        TtStats {
+            probes: self.probes.load(Ordering::Relaxed),
+            hits: self.hits.load(Ordering::Relaxed),
+            stores: self.stores.load(Ordering::Relaxed),
+            overwrites: self.overwrites.load(Ordering::Relaxed),
+        }
+    }
+
+    /// 已使用槽位的比例（0.0 ~ 1.0），用來評估置換表大小是否足夠
+    pub fn fill_rate(&self) -> f64 {
+        let used = self.entries.iter().filter(|e| Self::lock(e).is_some()).count();
+        used as f64 / self.entries.len() as f64
+    }
+}
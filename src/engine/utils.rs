@@ -1,7 +1,6 @@
 use rand::Rng;
 use super::types::Position;
 
-pub fn random_position(width: usize, height: usize) -> Position {
-    let mut rng = rand::thread_rng();
-    (rng.gen_range(0..width), rng.gen_range(0..height))
-}
\ No newline at end of file
+pub fn random_position(rng: &mut impl Rng, width: usize, height: usize) -> Position {
+    (rng.random_range(0..width), rng.random_range(0..height))
+}
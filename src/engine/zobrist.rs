@@ -0,0 +1,91 @@
+use super::board::Board;
+use super::castling::CastlingRights;
+use super::types::{Color, PieceType, Position};
+
+// 各類特徵的標籤，避免不同特徵在同一格產生相同的鍵
+const TAG_PIECE: u64 = 1;
+const TAG_BLOCKED: u64 = 2;
+const TAG_DIMENSIONS: u64 = 3;
+const TAG_SIDE: u64 = 4;
+const TAG_EN_PASSANT: u64 = 5;
+const TAG_CASTLING: u64 = 6;
+
+/// SplitMix64：把特徵編碼打散成 Zobrist 鍵
+/// 鍵由特徵直接算出而非查表，因此任何棋盤大小與自訂棋種都能使用，且每次執行結果相同
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+fn key(tag: u64, a: u64, b: u64) -> u64 {
+    splitmix64(splitmix64(splitmix64(tag) ^ a) ^ b)
+}
+
+fn square_code(pos: Position) -> u64 {
+    ((pos.0 as u64) << 32) | pos.1 as u64
+}
+
+fn kind_code(kind: PieceType) -> u64 {
+    match kind {
+        PieceType::King => 0,
+        PieceType::Queen => 1,
+        PieceType::Rook => 2,
+        PieceType::Bishop => 3,
+        PieceType::Knight => 4,
+        PieceType::Pawn => 5,
+        PieceType::Custom(id) => 6 + id as u64,
+    }
+}
+
+/// 棋子（棋種、顏色、格子）的鍵
+pub fn piece_key(kind: PieceType, color: Color, pos: Position) -> u64 {
+    let color_code = match color {
+        Color::White => 0,
+        Color::Black => 1,
+    };
+    key(TAG_PIECE, (kind_code(kind) << 1) | color_code, square_code(pos))
+}
+
+/// 禁區格子的鍵
+pub fn blocked_key(pos: Position) -> u64 {
+    key(TAG_BLOCKED, 0, square_code(pos))
+}
+
+/// 棋盤尺寸的鍵（不同尺寸的空棋盤雜湊值不同）
+pub fn dimensions_key(width: usize, height: usize) -> u64 {
+    key(TAG_DIMENSIONS, width as u64, height as u64)
+}
+
+/// 輪到黑方時加入的鍵
+pub fn side_key() -> u64 {
+    key(TAG_SIDE, 0, 0)
+}
+
+/// 過路兵目標格的鍵（沒有則為 0）
+pub fn en_passant_key(target: Option<Position>) -> u64 {
+    target.map_or(0, |pos| key(TAG_EN_PASSANT, 0, square_code(pos)))
+}
+
+/// 易位權利的鍵：每一項仍存在的權利（連同車的位置）各自加入
+pub fn castling_key(rights: &CastlingRights) -> u64 {
+    [
+        rights.white_king_side,
+        rights.white_queen_side,
+        rights.black_king_side,
+        rights.black_queen_side,
+    ]
+    .iter()
+    .enumerate()
+    .filter_map(|(slot, right)| right.map(|pos| key(TAG_CASTLING, slot as u64, square_code(pos))))
+    .fold(0, |acc, k| acc ^ k)
+}
+
+/// 包含輪到哪一方的局面雜湊值（置換表、重複局面偵測用）
+pub fn position_hash(board: &Board, side_to_move: Color) -> u64 {
+    match side_to_move {
+        Color::White => board.hash(),
+        Color::Black => board.hash() ^ side_key(),
+    }
+}
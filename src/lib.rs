@@ -1 +1,3 @@
-pub mod engine;
\ No newline at end of file
+#![allow(non_snake_case)]
+
+pub mod engine;
@@ -0,0 +1,74 @@
+#![cfg(feature = "bitboard")]
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use Chess_::engine::bitboard::{pseudo_legal_moves, Bitboard};
+use Chess_::engine::board::Board;
+use Chess_::engine::generator::{generate_random_board_with, generate_standard_board};
+use Chess_::engine::movement::array_pseudo_legal_moves;
+use Chess_::engine::piece::Piece;
+use Chess_::engine::types::{Color, PieceType};
+
+/// 每個棋子在兩種產生器下的目標格必須相同，順序也要一致
+fn assert_same_moves(board: &Board) {
+    let bits = board.bits().expect("16x16 以內應有位元棋盤");
+    for piece in board.pieces.values() {
+        let expected = array_pseudo_legal_moves(board, piece);
+        let actual = pseudo_legal_moves(board, bits, piece);
+        assert_eq!(actual, expected, "{:?} at {:?}", piece.kind, piece.pos);
+    }
+}
+
+#[test]
+fn test_matches_array_generator_on_random_boards() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for (width, height) in [(8, 8), (5, 7), (16, 16), (16, 3), (11, 16)] {
+        for _ in 0..20 {
+            let mut board = generate_random_board_with(&mut rng, width, height, 6, 6, 6);
+            board.pawn_double_step = true;
+            assert_same_moves(&board);
+        }
+    }
+    assert_same_moves(&generate_standard_board(16, 16));
+}
+
+#[test]
+fn test_matches_array_generator_with_en_passant() {
+    let mut board = Board::new(16, 16);
+    board.pawn_double_step = true;
+    board.add_piece(Piece { id: 0, kind: PieceType::Pawn, color: Color::White, pos: (15, 7) });
+    board.add_piece(Piece { id: 0, kind: PieceType::Pawn, color: Color::Black, pos: (14, 7) });
+    board.add_piece(Piece { id: 0, kind: PieceType::Pawn, color: Color::White, pos: (0, 14) });
+    board.en_passant = Some((14, 6));
+    assert_same_moves(&board);
+
+    let pawn = board.get_piece_at((15, 7)).unwrap();
+    assert!(pseudo_legal_moves(&board, board.bits().unwrap(), pawn).contains(&(14, 6)));
+}
+
+#[test]
+fn test_sliding_stops_at_blocked_and_edges() {
+    let mut board = Board::new(16, 16);
+    board.set_blocked((15, 3));
+    board.add_piece(Piece { id: 0, kind: PieceType::Rook, color: Color::White, pos: (15, 0) });
+    board.add_piece(Piece { id: 0, kind: PieceType::Bishop, color: Color::Black, pos: (0, 15) });
+    assert_same_moves(&board);
+
+    let rook = board.get_piece_at((15, 0)).unwrap();
+    let moves = pseudo_legal_moves(&board, board.bits().unwrap(), rook);
+    assert!(moves.contains(&(15, 2)));
+    assert!(!moves.contains(&(15, 3)), "禁區會擋住直線");
+    assert!(!moves.contains(&(0, 1)), "不可從右邊界繞回左邊");
+}
+
+#[test]
+fn test_large_board_falls_back() {
+    let board = Board::new(17, 8);
+    assert!(board.bits().is_none());
+
+    let mut bb = Bitboard::EMPTY;
+    bb.set((3, 4));
+    assert_eq!(bb.positions(), vec![(3, 4)]);
+    assert_eq!(bb.shift(-1, 0).positions(), vec![(2, 4)]);
+}
@@ -66,4 +66,30 @@ fn test_move_and_remove_piece() {
 
     board.remove_piece_at((2, 2));
     assert!(board.get_piece_at((2, 2)).is_none());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_square_index_stays_in_sync() {
+    let mut board = Board::new(6, 6);
+    let a = board.add_piece(Piece { id: 0, kind: PieceType::Rook, color: Color::White, pos: (0, 0) });
+    let b = board.add_piece(Piece { id: 0, kind: PieceType::Pawn, color: Color::Black, pos: (0, 3) });
+    assert_eq!(board.piece_id_at((0, 0)), Some(a));
+    assert_eq!(board.piece_id_at((0, 3)), Some(b));
+
+    // 放在已有棋子的格子上會失敗，索引不變
+    assert_eq!(board.add_piece(Piece { id: 0, kind: PieceType::Pawn, color: Color::Black, pos: (0, 0) }), u32::MAX);
+    assert_eq!(board.piece_id_at((0, 0)), Some(a));
+
+    board.remove_piece_at((0, 3));
+    board.move_piece(a, (0, 3));
+    assert_eq!(board.piece_id_at((0, 0)), None);
+    assert_eq!(board.get_piece_at((0, 3)).unwrap().id, a);
+
+    board.remove_piece(a);
+    assert!(board.get_piece_at((0, 3)).is_none());
+    assert_eq!(board.piece_id_at((9, 9)), None); // 超出邊界
+
+    board.add_piece(Piece { id: 0, kind: PieceType::King, color: Color::White, pos: (2, 2) });
+    board.clear_pieces();
+    assert!(board.get_piece_at((2, 2)).is_none());
+}
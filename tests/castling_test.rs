@@ -0,0 +1,122 @@
+use Chess_::engine::board::Board;
+use Chess_::engine::castling::CastlingRights;
+use Chess_::engine::types::{Color, PieceType};
+use Chess_::engine::movement::{get_all_legal_moves, get_legal_moves};
+use Chess_::engine::moves::Move;
+use Chess_::engine::game::GameState;
+use Chess_::engine::generator::generate_standard_board;
+
+mod common;
+use common::place;
+
+/// 只有王與兩個車的底線，方便測試易位
+fn castling_board(width: usize, height: usize) -> Board {
+    let mut board = Board::new(width, height);
+    let king_x = width / 2;
+    place(&mut board, PieceType::King, Color::White, (king_x, height - 1));
+    place(&mut board, PieceType::Rook, Color::White, (0, height - 1));
+    place(&mut board, PieceType::Rook, Color::White, (width - 1, height - 1));
+    place(&mut board, PieceType::King, Color::Black, (king_x, 0));
+    place(&mut board, PieceType::Rook, Color::Black, (0, 0));
+    place(&mut board, PieceType::Rook, Color::Black, (width - 1, 0));
+    board.castling = CastlingRights::detect(&board);
+    board
+}
+
+#[test]
+fn test_standard_board_has_all_castling_rights() {
+    let board = generate_standard_board(8, 8);
+    assert_eq!(board.castling.white_king_side, Some((7, 7)));
+    assert_eq!(board.castling.white_queen_side, Some((0, 7)));
+    assert_eq!(board.castling.black_king_side, Some((7, 0)));
+    assert_eq!(board.castling.black_queen_side, Some((0, 0)));
+    assert_eq!(board.get_piece_at((4, 7)).unwrap().kind, PieceType::King);
+    assert_eq!(board.get_piece_at((3, 0)).unwrap().kind, PieceType::Queen);
+
+    // 開局時中間有棋子擋住，不能易位
+    let king = board.get_piece_at((4, 7)).unwrap();
+    assert!(get_legal_moves(&board, king).is_empty());
+}
+
+#[test]
+fn test_standard_board_minimum_size() {
+    let board = generate_standard_board(5, 4);
+    assert_eq!(board.pieces.len(), 20);
+    assert_eq!(board.get_piece_at((2, 3)).unwrap().kind, PieceType::King);
+    assert_eq!(board.get_piece_at((2, 2)).unwrap().kind, PieceType::Pawn);
+
+    // 太小的棋盤會讓兵列與對方底線重疊，或在計算列數時溢位
+    for (width, height) in [(4, 8), (0, 8), (8, 3), (8, 1), (8, 0)] {
+        let result = std::panic::catch_unwind(|| generate_standard_board(width, height));
+        assert!(result.is_err(), "{}x{} 應該被拒絕", width, height);
+    }
+}
+
+#[test]
+fn test_castle_both_sides_moves_rook() {
+    let board = castling_board(8, 8);
+    let moves = get_all_legal_moves(&board, Color::White);
+    assert!(moves.contains(&Move::castle((4, 7), (6, 7))));
+    assert!(moves.contains(&Move::castle((4, 7), (2, 7))));
+
+    let mut game = GameState::new(board);
+    game.try_move((4, 7), (6, 7), None).unwrap();
+    assert_eq!(game.board.get_piece_at((6, 7)).unwrap().kind, PieceType::King);
+    assert_eq!(game.board.get_piece_at((5, 7)).unwrap().kind, PieceType::Rook);
+    assert!(game.board.get_piece_at((7, 7)).is_none());
+    assert_eq!(game.castling_rights().white_king_side, None);
+    assert_eq!(game.castling_rights().white_queen_side, None);
+
+    game.try_move((4, 0), (2, 0), None).unwrap();
+    assert_eq!(game.board.get_piece_at((2, 0)).unwrap().kind, PieceType::King);
+    assert_eq!(game.board.get_piece_at((3, 0)).unwrap().kind, PieceType::Rook);
+}
+
+#[test]
+fn test_castle_on_wide_board() {
+    // 寬度 10：王在 x = 5，王翼車在 x = 9
+    let board = castling_board(10, 8);
+    let king = board.get_piece_at((5, 7)).unwrap();
+    let moves = get_legal_moves(&board, king);
+    assert!(moves.contains(&(7, 7)));
+    assert!(moves.contains(&(3, 7)));
+
+    let mut game = GameState::new(board);
+    game.try_move((5, 7), (7, 7), None).unwrap();
+    assert_eq!(game.board.get_piece_at((6, 7)).unwrap().kind, PieceType::Rook);
+}
+
+#[test]
+fn test_blocked_tile_between_king_and_rook_prevents_castling() {
+    let mut board = castling_board(8, 8);
+    board.set_blocked((1, 7)); // 只在后翼車旁邊，王不會經過
+    let king = board.get_piece_at((4, 7)).unwrap();
+    let moves = get_legal_moves(&board, king);
+    assert!(!moves.contains(&(2, 7)));
+    assert!(moves.contains(&(6, 7)));
+}
+
+#[test]
+fn test_cannot_castle_out_of_or_through_check() {
+    let mut board = castling_board(8, 8);
+    place(&mut board, PieceType::Knight, Color::Black, (4, 5)); // 攻擊 (5, 7) 與 (3, 7)
+    let king = board.get_piece_at((4, 7)).unwrap();
+    let moves = get_legal_moves(&board, king);
+    assert!(!moves.contains(&(6, 7)), "不可經過被攻擊的格子");
+    assert!(!moves.contains(&(2, 7)), "不可經過被攻擊的格子");
+
+    let mut board = castling_board(8, 8);
+    place(&mut board, PieceType::Knight, Color::Black, (5, 5)); // 將軍
+    let king = board.get_piece_at((4, 7)).unwrap();
+    let moves = get_legal_moves(&board, king);
+    assert!(!moves.contains(&(6, 7)) && !moves.contains(&(2, 7)), "被將軍時不可易位");
+}
+
+#[test]
+fn test_rook_move_loses_only_that_side() {
+    let board = castling_board(8, 8);
+    let mut game = GameState::new(board);
+    game.try_move((7, 7), (7, 6), None).unwrap();
+    assert_eq!(game.castling_rights().white_king_side, None);
+    assert_eq!(game.castling_rights().white_queen_side, Some((0, 7)));
+}
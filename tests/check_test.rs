@@ -0,0 +1,71 @@
+use Chess_::engine::board::Board;
+use Chess_::engine::types::{Color, PieceType};
+use Chess_::engine::movement::{get_legal_moves, is_in_check};
+use Chess_::engine::game::{game_result, GameResult};
+
+mod common;
+use common::place;
+
+#[test]
+fn test_rook_gives_check() {
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::King, Color::White, (4, 7));
+    place(&mut board, PieceType::Rook, Color::Black, (4, 0));
+    assert!(is_in_check(&board, Color::White));
+    assert!(!is_in_check(&board, Color::Black));
+
+    // 禁區擋住直線時不算將軍
+    board.set_blocked((4, 3));
+    assert!(!is_in_check(&board, Color::White));
+}
+
+#[test]
+fn test_king_cannot_walk_into_check() {
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::King, Color::White, (4, 7));
+    place(&mut board, PieceType::Rook, Color::Black, (3, 0));
+
+    let king = board.get_piece_at((4, 7)).unwrap();
+    let moves = get_legal_moves(&board, king);
+    assert!(!moves.contains(&(3, 7)), "國王不可走進車的攻擊線");
+    assert!(!moves.contains(&(3, 6)));
+    assert!(moves.contains(&(5, 7)));
+}
+
+#[test]
+fn test_pinned_piece_cannot_leave_line() {
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::King, Color::White, (4, 7));
+    place(&mut board, PieceType::Knight, Color::White, (4, 5));
+    place(&mut board, PieceType::Rook, Color::Black, (4, 0));
+
+    let knight = board.get_piece_at((4, 5)).unwrap();
+    assert!(get_legal_moves(&board, knight).is_empty(), "被牽制的騎士不能移動");
+}
+
+#[test]
+fn test_back_rank_checkmate() {
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::King, Color::Black, (7, 0));
+    place(&mut board, PieceType::Pawn, Color::Black, (6, 1));
+    place(&mut board, PieceType::Pawn, Color::Black, (7, 1));
+    place(&mut board, PieceType::Rook, Color::White, (0, 0));
+    place(&mut board, PieceType::King, Color::White, (0, 7));
+
+    assert_eq!(
+        game_result(&board, Color::Black),
+        Some(GameResult::Checkmate { winner: Color::White })
+    );
+    assert_eq!(game_result(&board, Color::White), None);
+}
+
+#[test]
+fn test_stalemate() {
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::Queen, Color::White, (1, 2));
+    place(&mut board, PieceType::King, Color::White, (7, 7));
+
+    assert!(!is_in_check(&board, Color::Black));
+    assert_eq!(game_result(&board, Color::Black), Some(GameResult::Stalemate));
+}
@@ -0,0 +1,35 @@
+use Chess_::engine::board::Board;
+use Chess_::engine::generator::{find_mate_in, generate_random_board_with};
+use Chess_::engine::minmax::SearchResult;
+use Chess_::engine::piece::Piece;
+use Chess_::engine::types::{Color, PieceType};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// 在棋盤上放一個棋子（ID 由棋盤分配）
+pub fn place(board: &mut Board, kind: PieceType, color: Color, pos: (usize, usize)) {
+    board.add_piece(Piece { id: 0, kind, color, pos });
+}
+
+/// 以固定種子產生 `count` 個白方先走、最短恰好 `mate_in` 步將死的題目
+/// 最多嘗試固定次數，因此執行時間有上限且每次結果相同
+#[allow(dead_code)]
+pub fn seeded_mate_puzzles(
+    seed: u64,
+    count: usize,
+    (width, height): (usize, usize),
+    (num_white, num_black, num_blocked): (usize, usize, usize),
+    mate_in: usize,
+) -> Vec<(Board, SearchResult)> {
+    const MAX_ATTEMPTS: usize = 2000;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let puzzles: Vec<_> = (0..MAX_ATTEMPTS)
+        .filter_map(|_| {
+            let board = generate_random_board_with(&mut rng, width, height, num_white, num_black, num_blocked);
+            find_mate_in(&board, mate_in).map(|result| (board, result))
+        })
+        .take(count)
+        .collect();
+    assert_eq!(puzzles.len(), count, "嘗試 {} 次仍找不到足夠的題目", MAX_ATTEMPTS);
+    puzzles
+}
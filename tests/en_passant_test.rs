@@ -0,0 +1,92 @@
+use Chess_::engine::board::Board;
+use Chess_::engine::types::{Color, PieceType};
+use Chess_::engine::movement::get_legal_moves;
+use Chess_::engine::game::GameState;
+
+mod common;
+use common::place;
+
+#[test]
+fn test_double_step_is_optional() {
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::Pawn, Color::White, (3, 6));
+
+    let pawn = board.get_piece_at((3, 6)).unwrap();
+    assert!(!get_legal_moves(&board, pawn).contains(&(3, 4)), "預設不可前進兩格");
+
+    board.pawn_double_step = true;
+    let pawn = board.get_piece_at((3, 6)).unwrap();
+    let moves = get_legal_moves(&board, pawn);
+    assert!(moves.contains(&(3, 5)));
+    assert!(moves.contains(&(3, 4)));
+}
+
+#[test]
+fn test_double_step_only_from_start_rank_of_board() {
+    // 高度 6 的棋盤：白兵起始列 y = 4，黑兵起始列 y = 1
+    let mut board = Board::new(6, 6);
+    board.pawn_double_step = true;
+    place(&mut board, PieceType::Pawn, Color::White, (0, 4));
+    place(&mut board, PieceType::Pawn, Color::White, (1, 3));
+    place(&mut board, PieceType::Pawn, Color::Black, (5, 1));
+
+    let moves = get_legal_moves(&board, board.get_piece_at((0, 4)).unwrap());
+    assert!(moves.contains(&(0, 2)));
+    let moves = get_legal_moves(&board, board.get_piece_at((1, 3)).unwrap());
+    assert!(!moves.contains(&(1, 1)), "不在起始列不可前進兩格");
+    let moves = get_legal_moves(&board, board.get_piece_at((5, 1)).unwrap());
+    assert!(moves.contains(&(5, 3)));
+}
+
+#[test]
+fn test_double_step_blocked_by_tile_in_path() {
+    let mut board = Board::new(8, 8);
+    board.pawn_double_step = true;
+    place(&mut board, PieceType::Pawn, Color::White, (2, 6));
+    place(&mut board, PieceType::Pawn, Color::White, (5, 6));
+    board.set_blocked((2, 5)); // 擋住路徑
+    board.set_blocked((5, 4)); // 擋住終點
+
+    let moves = get_legal_moves(&board, board.get_piece_at((2, 6)).unwrap());
+    assert!(moves.is_empty(), "路徑被禁區擋住時不可跳過");
+    let moves = get_legal_moves(&board, board.get_piece_at((5, 6)).unwrap());
+    assert_eq!(moves, vec![(5, 5)]);
+}
+
+#[test]
+fn test_en_passant_capture_and_expiry() {
+    let mut board = Board::new(8, 8);
+    board.pawn_double_step = true;
+    place(&mut board, PieceType::King, Color::White, (7, 7));
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::Pawn, Color::White, (4, 3));
+    place(&mut board, PieceType::Pawn, Color::Black, (3, 1));
+    place(&mut board, PieceType::Rook, Color::Black, (6, 0));
+    let mut game = GameState::new(board);
+
+    game.turn = game.turn.switch();
+    game.try_move((3, 1), (3, 3), None).unwrap();
+    assert_eq!(game.en_passant_target(), Some((3, 2)));
+
+    game.try_move((4, 3), (3, 2), None).unwrap();
+    assert!(game.board.get_piece_at((3, 3)).is_none(), "被吃的兵應被移除");
+    assert_eq!(game.board.get_piece_at((3, 2)).unwrap().color, Color::White);
+    assert_eq!(game.en_passant_target(), None);
+}
+
+#[test]
+fn test_en_passant_not_allowed_when_it_exposes_king() {
+    let mut board = Board::new(8, 8);
+    board.pawn_double_step = true;
+    // 白王與黑車在同一列，中間只有雙方兩個兵
+    place(&mut board, PieceType::King, Color::White, (0, 3));
+    place(&mut board, PieceType::Pawn, Color::White, (4, 3));
+    place(&mut board, PieceType::Rook, Color::Black, (7, 3));
+    place(&mut board, PieceType::King, Color::Black, (7, 7));
+    place(&mut board, PieceType::Pawn, Color::Black, (3, 1));
+    let mut game = GameState::new(board);
+
+    game.turn = game.turn.switch();
+    game.try_move((3, 1), (3, 3), None).unwrap();
+    assert!(game.try_move((4, 3), (3, 2), None).is_err());
+}
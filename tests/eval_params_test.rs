@@ -0,0 +1,108 @@
+use std::fs;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use Chess_::engine::board::Board;
+use Chess_::engine::eval_params::{EvalParams, EvalParamsError};
+use Chess_::engine::evaluator::{evaluate_board, DefaultEvaluator, Evaluator};
+use Chess_::engine::generator::generate_random_board_with;
+use Chess_::engine::types::{Color, PieceType};
+
+mod common;
+use common::place;
+
+#[test]
+fn test_default_params_match_default_evaluation() {
+    let mut rng = StdRng::seed_from_u64(24);
+    let evaluator = DefaultEvaluator::new(EvalParams::default());
+    for _ in 0..10 {
+        let board = generate_random_board_with(&mut rng, 8, 8, 5, 5, 5);
+        assert_eq!(evaluator.evaluate(&board), evaluate_board(&board));
+    }
+}
+
+#[test]
+fn test_toml_overrides_only_given_fields() {
+    let params = EvalParams::from_toml(
+        r#"
+        [pieces]
+        queen = 10
+        knight = 4
+
+        [pieces.custom]
+        7 = 6
+
+        [king_safety]
+        edge_bonus = 3
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(params.pieces.value(PieceType::Queen), 10);
+    assert_eq!(params.pieces.value(PieceType::Knight), 4);
+    assert_eq!(params.pieces.value(PieceType::Rook), 5, "沒寫到的欄位沿用預設值");
+    assert_eq!(params.pieces.value(PieceType::Custom(7)), 6);
+    assert_eq!(params.pieces.value(PieceType::Custom(2)), 2, "沒列出的自訂棋種以編號計分");
+    assert_eq!(params.king_safety.edge_bonus, 3);
+    assert_eq!(params.king_safety.center_penalty, 1);
+    assert_eq!(params.positional, EvalParams::default().positional);
+}
+
+#[test]
+fn test_json_params_change_evaluation() {
+    let params = EvalParams::from_json(
+        r#"{
+            "pieces": { "custom": { "3": 8 } },
+            "positional": { "weight": 0 },
+            "king_safety": { "edge_bonus": 0, "center_penalty": 0 }
+        }"#,
+    )
+    .unwrap();
+
+    // 位置與王的安全性權重都是 0，只剩物料
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::White, (0, 0));
+    place(&mut board, PieceType::Custom(3), Color::White, (2, 2));
+    place(&mut board, PieceType::King, Color::Black, (3, 3));
+    place(&mut board, PieceType::Rook, Color::Black, (5, 5));
+
+    let evaluator = DefaultEvaluator::new(params);
+    assert_eq!(evaluator.evaluate(&board), 8 - 5);
+    let breakdown = evaluator.breakdown(&board);
+    assert_eq!(breakdown.terms.positional, 0);
+    assert_eq!(breakdown.terms.king_safety, 0);
+    assert_eq!(breakdown.total, 3);
+}
+
+#[test]
+fn test_load_by_extension() {
+    let dir = std::env::temp_dir().join(format!("chess_eval_params_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let mut params = EvalParams::default();
+    params.pieces.bishop = 4;
+    params.pieces.custom.insert(9, 12);
+
+    let toml_path = dir.join("params.toml");
+    fs::write(&toml_path, "[pieces]\nbishop = 4\n\n[pieces.custom]\n9 = 12\n").unwrap();
+    assert_eq!(EvalParams::load(&toml_path).unwrap(), params);
+
+    let json_path = dir.join("params.json");
+    fs::write(&json_path, r#"{ "pieces": { "bishop": 4, "custom": { "9": 12 } } }"#).unwrap();
+    assert_eq!(EvalParams::load(&json_path).unwrap(), params);
+
+    let txt_path = dir.join("params.txt");
+    fs::write(&txt_path, "").unwrap();
+    assert!(matches!(EvalParams::load(&txt_path), Err(EvalParamsError::UnsupportedFormat(_))));
+    assert!(matches!(EvalParams::load(dir.join("missing.toml")), Err(EvalParamsError::Io(_))));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_invalid_files_are_errors() {
+    assert!(matches!(EvalParams::from_toml("[pieces]\nqueen = \"nine\""), Err(EvalParamsError::Toml(_))));
+    assert!(matches!(EvalParams::from_json("{ \"pieces\": 9 }"), Err(EvalParamsError::Json(_))));
+    assert_eq!(EvalParams::from_toml("").unwrap(), EvalParams::default());
+}
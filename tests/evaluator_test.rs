@@ -0,0 +1,189 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use Chess_::engine::board::Board;
+use Chess_::engine::eval_params::EvalParams;
+use Chess_::engine::evaluator::{
+    evaluate_board, evaluate_board_verbose, piece_value, DefaultEvaluator, EvalBreakdown, Evaluator, MaterialEvaluator,
+    PieceEval,
+};
+use Chess_::engine::generator::{generate_random_board_with, generate_standard_board};
+use Chess_::engine::minmax::{SearchLimits, Searcher};
+use Chess_::engine::movement::get_all_legal_moves;
+use Chess_::engine::types::{Color, PieceType};
+
+mod common;
+use common::place;
+
+/// 白方的馬越靠近 (0, 0) 越好，其餘一律不計分
+struct KnightToCorner;
+
+impl Evaluator for KnightToCorner {
+    fn evaluate(&self, board: &Board) -> i32 {
+        board
+            .pieces
+            .values()
+            .filter(|p| p.kind == PieceType::Knight && p.color == Color::White)
+            .map(|p| -((p.pos.0 + p.pos.1) as i32))
+            .sum()
+    }
+}
+
+/// 計算被呼叫次數的物料評估
+#[derive(Default)]
+struct CountingEvaluator {
+    calls: AtomicU64,
+}
+
+impl Evaluator for CountingEvaluator {
+    fn evaluate(&self, board: &Board) -> i32 {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        MaterialEvaluator.evaluate(board)
+    }
+}
+
+#[test]
+fn test_default_evaluator_matches_functions() {
+    let mut rng = StdRng::seed_from_u64(22);
+    for _ in 0..10 {
+        let board = generate_random_board_with(&mut rng, 8, 8, 5, 5, 5);
+        let score = DefaultEvaluator::default().evaluate(&board);
+        assert_eq!(evaluate_board(&board), score);
+        assert_eq!(evaluate_board_verbose(&board).total, score);
+    }
+}
+
+#[test]
+fn test_material_evaluator() {
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::White, (0, 0));
+    place(&mut board, PieceType::Rook, Color::White, (2, 2));
+    place(&mut board, PieceType::King, Color::Black, (5, 5));
+    place(&mut board, PieceType::Knight, Color::Black, (3, 3));
+    assert_eq!(MaterialEvaluator.evaluate(&board), 5 - 3);
+}
+
+#[test]
+fn test_breakdown_terms() {
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::White, (0, 0));
+    place(&mut board, PieceType::Knight, Color::Black, (3, 3));
+    place(&mut board, PieceType::King, Color::Black, (5, 5));
+    let breakdown = evaluate_board_verbose(&board);
+
+    // 位於中心的黑騎士：物料 3、位置 +1，以白方觀點為 -4
+    let knight = PieceEval {
+        kind: PieceType::Knight,
+        color: Color::Black,
+        pos: (3, 3),
+        material: 3,
+        positional: 1,
+        king_safety: 0,
+        total: -4,
+    };
+    assert_eq!(breakdown.pieces.len(), 3);
+    assert_eq!(breakdown.pieces[1], knight);
+    assert_eq!(breakdown.pieces[0].king_safety, 0, "只剩一個騎士的殘局幾乎不計王的安全性");
+
+    assert_eq!(breakdown.terms.material, -3);
+    assert_eq!(breakdown.terms.total(), breakdown.total);
+    assert_eq!(breakdown.pieces.iter().map(|p| p.total).sum::<i32>(), breakdown.total);
+    assert_eq!(breakdown.total, evaluate_board(&board));
+}
+
+#[test]
+fn test_breakdown_serializes() {
+    let mut rng = StdRng::seed_from_u64(23);
+    let board = generate_random_board_with(&mut rng, 8, 8, 5, 5, 5);
+    let breakdown = DefaultEvaluator::default().breakdown(&board);
+    let json = serde_json::to_string(&breakdown).unwrap();
+    assert!(json.contains("\"king_safety\""));
+    let parsed: EvalBreakdown = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, breakdown);
+
+    let text = breakdown.to_string();
+    assert!(text.starts_with("棋子評分明細："));
+    assert!(text.ends_with(&format!("總分：{}", breakdown.total)));
+}
+
+#[test]
+fn test_king_safety_fades_in_endgame() {
+    // 中局：王在底線邊上較安全
+    let breakdown = evaluate_board_verbose(&generate_standard_board(8, 8));
+    let kings: Vec<_> = breakdown.pieces.iter().filter(|p| p.kind == PieceType::King).collect();
+    assert!(kings.iter().all(|king| king.king_safety == 1), "{:?}", kings);
+
+    // 只剩雙王的殘局：白王從邊線往中央走，評分越來越高
+    let scores: Vec<i32> = (0..3)
+        .map(|x| {
+            let mut board = Board::new(8, 8);
+            place(&mut board, PieceType::King, Color::White, (x, 3));
+            place(&mut board, PieceType::King, Color::Black, (7, 0));
+            let breakdown = evaluate_board_verbose(&board);
+            assert_eq!(breakdown.terms.king_safety, 0);
+            breakdown.total
+        })
+        .collect();
+    assert!(scores.windows(2).all(|w| w[0] < w[1]), "{:?}", scores);
+}
+
+#[test]
+fn test_search_uses_plugged_evaluator() {
+    // 預設評估不在意馬的位置，換成自訂評估後馬要走向角落
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::White, (5, 5));
+    place(&mut board, PieceType::King, Color::Black, (5, 0));
+    place(&mut board, PieceType::Knight, Color::White, (2, 2));
+
+    let mut searcher = Searcher::with_evaluator(1 << 12, KnightToCorner);
+    searcher.options.quiescence = false;
+    let mv = searcher.choose_best_move(&board, Color::White, 1).unwrap();
+    assert_eq!(mv.from, (2, 2));
+    assert!(mv.to == (0, 1) || mv.to == (1, 0), "{:?}", mv.to);
+}
+
+/// 以「分」為單位的物料評估（兵 = 100），吃子價值也以同一尺度回報
+struct CentipawnMaterial;
+
+impl Evaluator for CentipawnMaterial {
+    fn evaluate(&self, board: &Board) -> i32 {
+        100 * MaterialEvaluator.evaluate(board)
+    }
+
+    fn piece_value(&self, kind: PieceType) -> i32 {
+        100 * piece_value(kind)
+    }
+}
+
+#[test]
+fn test_search_scales_with_evaluator_units() {
+    // Delta 剪枝與渴望窗口依評估器的兵值換算：分數放大 100 倍時，搜尋結果也只是放大 100 倍
+    let mut rng = StdRng::seed_from_u64(24);
+    let mut boards = vec![generate_standard_board(8, 8)];
+    boards.extend((0..6).map(|_| generate_random_board_with(&mut rng, 6, 6, 5, 5, 2)));
+    for board in boards {
+        let pawns = Searcher::with_evaluator(1 << 14, MaterialEvaluator).search(&board, Color::White, SearchLimits::depth(3));
+        let cents = Searcher::with_evaluator(1 << 14, CentipawnMaterial).search(&board, Color::White, SearchLimits::depth(3));
+        if pawns.mate_in().is_some() {
+            continue;
+        }
+        assert_eq!(cents.best_move, pawns.best_move);
+        assert_eq!(cents.score, 100 * pawns.score);
+    }
+
+    let mut params = EvalParams::default();
+    params.pieces.queen = 900;
+    assert_eq!(DefaultEvaluator::new(params).piece_value(PieceType::Queen), 900);
+}
+
+#[test]
+fn test_evaluator_shared_by_threads() {
+    let board = generate_standard_board(8, 8);
+    let mut searcher = Searcher::with_evaluator(1 << 14, CountingEvaluator::default());
+    let result = searcher.search(&board, Color::White, SearchLimits::depth(3).with_threads(2));
+
+    assert!(get_all_legal_moves(&board, Color::White).contains(&result.best_move.unwrap()));
+    assert!(searcher.evaluator().calls.load(Ordering::Relaxed) > 0);
+}
@@ -1,8 +1,13 @@
 use Chess_::engine::generator::generate_random_board;
-use Chess_::engine::generator::generate_by_difficulty;
+use Chess_::engine::generator::{generate_by_difficulty, meets_difficulty};
+use Chess_::engine::board::Board;
+use Chess_::engine::types::{Color, PieceType};
 // use Chess_::engine::evaluator::evaluate_board;
 use Chess_::engine::evaluator::evaluate_board_verbose;
 
+mod common;
+use common::{place, seeded_mate_puzzles};
+
 #[test]
 fn test_random_generation_and_evaluation() {
     // 生成 8x8 棋盤，含 5 白子、5 黑子、5 禁區
@@ -17,19 +22,37 @@ fn test_random_generation_and_evaluation() {
     println!("[評分結果] 該殘局分數為：{}", score);
 
     // 測試成功條件：只是確認能正常執行、不 panic
-    assert!(true);
 }
 
 #[test]
 fn test_generate_by_difficulty() {
     let difficulty_threshold = -5; // 對白方越困難
-    let board = generate_by_difficulty(8, 8, 5, 5, 5, difficulty_threshold);
+    let board = generate_by_difficulty(8, 8, 5, 5, 5, difficulty_threshold, None);
 
     println!("[根據難度閾值生成殘局，白方分數 <= {}]", difficulty_threshold);
     board.print_board();
 
-    let score = evaluate_board_verbose(&board);
+    let breakdown = evaluate_board_verbose(&board);
+    println!("{}", breakdown);
+    let score = breakdown.total;
     println!("[評分結果] 該殘局分數為：{}", score);
 
     assert!(score <= difficulty_threshold, "評分應符合難度閾值");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_difficulty_with_mate_target() {
+    // 白車一步走到底線將死黑王
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::King, Color::White, (0, 2));
+    place(&mut board, PieceType::Rook, Color::White, (5, 5));
+    assert!(meets_difficulty(&board, i32::MAX, Some(1)));
+    assert!(!meets_difficulty(&board, i32::MAX, Some(2)), "最短將死必須恰好是 N 步");
+    assert!(!meets_difficulty(&board, 0, Some(1)), "評分閾值仍然適用");
+
+    for (board, _) in seeded_mate_puzzles(24, 2, (5, 5), (4, 1, 0), 2) {
+        assert!(meets_difficulty(&board, i32::MAX, Some(2)));
+        assert!(!meets_difficulty(&board, i32::MAX, Some(1)));
+    }
+}
@@ -0,0 +1,121 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use Chess_::engine::board::Board;
+use Chess_::engine::castling::CastlingRights;
+use Chess_::engine::types::{Color, PieceType};
+use Chess_::engine::movement::get_all_legal_moves;
+use Chess_::engine::moves::{Move, MoveKind};
+use Chess_::engine::generator::{generate_random_board_with, generate_standard_board};
+
+mod common;
+use common::place;
+
+/// 對每一步合法走法執行 make/unmake，確認棋盤完全還原
+fn assert_round_trip(board: &Board, color: Color) {
+    let mut work = board.clone();
+    for mv in get_all_legal_moves(board, color) {
+        let undo = work.make_move(&mv);
+        work.unmake_move(&mv, undo);
+        assert_eq!(work.pieces, board.pieces, "{:?} 還原後棋子不同", mv);
+        assert_eq!(work.en_passant, board.en_passant);
+        assert_eq!(work.castling, board.castling);
+        assert_eq!(work.next_id, board.next_id);
+    }
+}
+
+#[test]
+fn test_round_trip_standard_and_random_boards() {
+    let board = generate_standard_board(8, 8);
+    assert_round_trip(&board, Color::White);
+    assert_round_trip(&board, Color::Black);
+
+    let mut rng = StdRng::seed_from_u64(5);
+    for _ in 0..10 {
+        let board = generate_random_board_with(&mut rng, 8, 8, 6, 6, 5);
+        assert_round_trip(&board, Color::White);
+        assert_round_trip(&board, Color::Black);
+    }
+}
+
+#[test]
+fn test_round_trip_special_moves() {
+    let mut board = Board::new(8, 8);
+    board.pawn_double_step = true;
+    place(&mut board, PieceType::King, Color::White, (4, 7));
+    place(&mut board, PieceType::Rook, Color::White, (0, 7));
+    place(&mut board, PieceType::Rook, Color::White, (7, 7));
+    place(&mut board, PieceType::Pawn, Color::White, (4, 3));
+    place(&mut board, PieceType::Pawn, Color::White, (1, 1));
+    place(&mut board, PieceType::King, Color::Black, (4, 0));
+    place(&mut board, PieceType::Knight, Color::Black, (0, 0)); // 升變時可吃
+    place(&mut board, PieceType::Pawn, Color::Black, (3, 3));
+    board.castling = CastlingRights::detect(&board);
+    board.en_passant = Some((3, 2));
+
+    let moves = get_all_legal_moves(&board, Color::White);
+    assert!(moves.iter().any(|m| m.kind == MoveKind::Castle));
+    assert!(moves.iter().any(|m| m.kind == MoveKind::EnPassant));
+    assert!(moves.iter().any(|m| m.promotion.is_some() && m.is_capture()));
+    assert_round_trip(&board, Color::White);
+}
+
+#[test]
+fn test_generated_moves_record_captures() {
+    let mut board = Board::new(8, 8);
+    board.pawn_double_step = true;
+    place(&mut board, PieceType::Pawn, Color::White, (4, 3));
+    place(&mut board, PieceType::Pawn, Color::White, (6, 6));
+    place(&mut board, PieceType::Knight, Color::Black, (5, 2));
+    place(&mut board, PieceType::Pawn, Color::Black, (3, 3));
+    board.en_passant = Some((3, 2));
+
+    let moves = get_all_legal_moves(&board, Color::White);
+    let capture = moves.iter().find(|m| m.to == (5, 2)).unwrap();
+    assert_eq!(capture.captured.unwrap().kind, PieceType::Knight);
+
+    let en_passant = moves.iter().find(|m| m.to == (3, 2)).unwrap();
+    assert_eq!(en_passant.kind, MoveKind::EnPassant);
+    assert_eq!(en_passant.captured.unwrap().pos, (3, 3));
+
+    let double = moves.iter().find(|m| m.to == (6, 4)).unwrap();
+    assert_eq!(double.kind, MoveKind::DoubleStep);
+    assert!(!double.is_capture());
+
+    let mut work = board.clone();
+    work.make_move(en_passant);
+    assert!(work.get_piece_at((3, 3)).is_none());
+}
+
+#[test]
+fn test_hand_built_capture_removes_piece() {
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::Rook, Color::White, (0, 7));
+    place(&mut board, PieceType::Knight, Color::Black, (0, 2));
+    let before = board.clone();
+
+    // Move::new 不帶 captured，被吃的棋子由棋盤判斷
+    let mv = Move::new((0, 7), (0, 2));
+    let undo = board.make_move(&mv);
+    assert_eq!(board.pieces.len(), 1);
+    assert_eq!(board.get_piece_at((0, 2)).unwrap().kind, PieceType::Rook);
+    assert_eq!(board.hash(), board.compute_hash());
+
+    board.unmake_move(&mv, undo);
+    assert_eq!(board.pieces, before.pieces);
+    assert_eq!(board.hash(), before.hash());
+}
+
+#[test]
+fn test_move_from_empty_square_is_ignored() {
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::Rook, Color::White, (0, 2));
+    let before = board.clone();
+
+    let mv = Move::new((5, 5), (0, 2));
+    let undo = board.make_move(&mv);
+    assert_eq!(board.pieces, before.pieces);
+    board.unmake_move(&mv, undo);
+    assert_eq!(board.pieces, before.pieces);
+    assert_eq!(board.get_piece_at((0, 2)).unwrap().pos, (0, 2));
+}
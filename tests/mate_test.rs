@@ -0,0 +1,99 @@
+use Chess_::engine::board::Board;
+use Chess_::engine::game::{game_result, GameResult};
+use Chess_::engine::mate::{analyze_mate, solve_mate};
+use Chess_::engine::movement::get_all_legal_moves;
+use Chess_::engine::moves::Move;
+use Chess_::engine::types::{Color, PieceType};
+
+mod common;
+use common::{place, seeded_mate_puzzles};
+
+/// 解答必須是合法的走法序列，且最後一步將死守方
+fn assert_mating_line(board: &Board, side: Color, line: &[Move]) {
+    let mut work = board.clone();
+    let mut to_move = side;
+    for mv in line {
+        assert!(get_all_legal_moves(&work, to_move).contains(mv), "{:?} 不合法", mv);
+        work.make_move(mv);
+        to_move = to_move.opposite();
+    }
+    assert_eq!(to_move, side.opposite());
+    assert_eq!(game_result(&work, to_move), Some(GameResult::Checkmate { winner: side }));
+}
+
+#[test]
+fn test_unique_back_rank_mate() {
+    // 黑王在角落被白王擋住，只有車走到底線能將死
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::King, Color::White, (0, 2));
+    place(&mut board, PieceType::Rook, Color::White, (5, 5));
+
+    let solution = analyze_mate(&board, Color::White, 3).unwrap();
+    assert_eq!(solution.moves(), 1);
+    assert!(solution.is_unique());
+    assert_eq!(solution.key_moves[0].to, (5, 0));
+    assert_mating_line(&board, Color::White, &solution.line);
+}
+
+#[test]
+fn test_several_key_moves_are_not_unique() {
+    // 后走到 (0, 5) 或 (1, 1) 都能將死
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::King, Color::White, (2, 1));
+    place(&mut board, PieceType::Queen, Color::White, (5, 5));
+
+    let solution = analyze_mate(&board, Color::White, 1).unwrap();
+    assert!(!solution.is_unique());
+    let targets: Vec<_> = solution.key_moves.iter().map(|m| m.to).collect();
+    assert!(targets.contains(&(0, 5)));
+    assert!(targets.contains(&(1, 1)));
+    assert_mating_line(&board, Color::White, &solution.line);
+}
+
+#[test]
+fn test_no_mate_and_stalemate_is_not_mate() {
+    // 只剩雙王：不可能將死
+    let mut board = Board::new(5, 5);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::King, Color::White, (4, 4));
+    assert_eq!(solve_mate(&board, Color::White, 3), None);
+
+    // 后走到 (1, 2) 會困住黑王但沒有將軍，是逼和而不是將死
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::King, Color::White, (2, 1));
+    place(&mut board, PieceType::Queen, Color::White, (3, 2));
+    board.set_blocked((0, 2));
+    let mut stalemate = board.clone();
+    stalemate.make_move(&Move::new((3, 2), (1, 2)));
+    assert_eq!(game_result(&stalemate, Color::Black), Some(GameResult::Stalemate));
+    assert_eq!(solve_mate(&board, Color::White, 1), None);
+}
+
+#[test]
+fn test_blocked_tiles_change_the_answer() {
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::King, Color::White, (0, 2));
+    place(&mut board, PieceType::Rook, Color::White, (5, 5));
+    assert!(solve_mate(&board, Color::White, 1).is_some());
+
+    // 禁區擋住車到底線的路
+    board.set_blocked((5, 3));
+    assert_eq!(solve_mate(&board, Color::White, 1), None);
+}
+
+#[test]
+fn test_matches_search_on_generated_puzzles() {
+    // 搜尋產生的兩步將死題目，窮舉證明也必須同意：沒有一步將死、有兩步將死
+    for (board, _) in seeded_mate_puzzles(15, 3, (5, 7), (4, 1, 3), 2) {
+        assert_eq!(solve_mate(&board, Color::White, 1), None);
+        let solution = analyze_mate(&board, Color::White, 2).unwrap();
+        assert_eq!(solution.moves(), 2);
+        assert_eq!(solution.line.len(), 3);
+        assert!(!solution.key_moves.is_empty());
+        assert_mating_line(&board, Color::White, &solution.line);
+    }
+}
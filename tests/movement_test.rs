@@ -117,7 +117,7 @@ fn test_pawn_moves_forward_and_capture() {
     });
 
     let pawn_ref = board.get_piece_at((3, 3)).unwrap();
-    let moves = get_legal_moves(&board, &pawn_ref);
+    let moves = get_legal_moves(&board, pawn_ref);
     assert!(moves.contains(&(3, 2)), "Pawn should be able to move forward");
     assert!(moves.contains(&(2, 2)), "Pawn should be able to capture left");
     assert!(moves.contains(&(4, 2)), "Pawn should be able to capture right");
@@ -136,7 +136,7 @@ fn test_king_moves_respects_boundaries_and_blocked() {
     board.set_blocked((0, 1));
 
     let king_ref = board.get_piece_at((0, 0)).unwrap();
-    let moves = get_legal_moves(&board, &king_ref);
+    let moves = get_legal_moves(&board, king_ref);
     assert!(moves.contains(&(1, 0)));
     assert!(moves.contains(&(1, 1)));
     assert!(!moves.contains(&(0, 1)), "Should not move into blocked tile");
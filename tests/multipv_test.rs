@@ -0,0 +1,111 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use Chess_::engine::board::Board;
+use Chess_::engine::generator::{generate_random_board_with, generate_standard_board};
+use Chess_::engine::minmax::{choose_top_moves, SearchLimits, SearchOptions, Searcher};
+use Chess_::engine::movement::get_all_legal_moves;
+use Chess_::engine::moves::Move;
+use Chess_::engine::types::{Color, PieceType};
+
+mod common;
+use common::place;
+
+/// 主要變例必須是從目前局面開始、雙方輪流的合法走法序列
+fn assert_legal_line(board: &Board, color: Color, pv: &[Move]) {
+    let mut work = board.clone();
+    let mut side = color;
+    for mv in pv {
+        assert!(get_all_legal_moves(&work, side).contains(mv), "{:?} 不合法", mv);
+        work.make_move(mv);
+        side = side.opposite();
+    }
+}
+
+#[test]
+fn test_top_moves_are_distinct_and_sorted() {
+    let board = generate_standard_board(8, 8);
+    let lines = choose_top_moves(&board, Color::White, 3, 4);
+
+    assert_eq!(lines.len(), 4);
+    for (i, line) in lines.iter().enumerate() {
+        assert_eq!(line.pv.first(), Some(&line.best_move));
+        assert_legal_line(&board, Color::White, &line.pv);
+        assert!(lines[..i].iter().all(|other| other.best_move != line.best_move), "第一步重複");
+    }
+    assert!(lines.windows(2).all(|w| w[0].score >= w[1].score));
+}
+
+#[test]
+fn test_first_line_matches_single_pv() {
+    // 不剪枝時，MultiPV 的第一條變例分數與一般搜尋相同
+    let exact = SearchOptions { quiescence: false, ..SearchOptions::exact() };
+    let mut rng = StdRng::seed_from_u64(19);
+    for _ in 0..5 {
+        let board = generate_random_board_with(&mut rng, 6, 6, 4, 4, 3);
+        if get_all_legal_moves(&board, Color::White).is_empty() {
+            continue;
+        }
+        let mut single = Searcher::new(1 << 14);
+        single.options = exact;
+        let result = single.search(&board, Color::White, SearchLimits::depth(3));
+
+        let mut multi = Searcher::new(1 << 14);
+        multi.options = SearchOptions { multi_pv: 3, ..exact };
+        let analysis = multi.search(&board, Color::White, SearchLimits::depth(3));
+        assert_eq!(analysis.score, result.score);
+        assert_eq!(analysis.lines[0].score, result.score);
+        assert_eq!(analysis.best_move, Some(analysis.lines[0].best_move));
+    }
+}
+
+#[test]
+fn test_unique_and_multiple_mates() {
+    // 只有車走到底線能一步將死
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::King, Color::White, (0, 2));
+    place(&mut board, PieceType::Rook, Color::White, (5, 5));
+    let lines = choose_top_moves(&board, Color::White, 3, 2);
+    assert_eq!(lines[0].mate_in(), Some(1));
+    assert_eq!(lines[0].best_move.to, (5, 0));
+    assert_ne!(lines[1].mate_in(), Some(1));
+
+    // 后走到 (0, 5) 或 (1, 1) 都能一步將死
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::King, Color::White, (2, 1));
+    place(&mut board, PieceType::Queen, Color::White, (5, 5));
+    let lines = choose_top_moves(&board, Color::White, 3, 3);
+    let mates: Vec<_> = lines.iter().filter(|line| line.mate_in() == Some(1)).map(|line| line.best_move.to).collect();
+    assert!(mates.contains(&(0, 5)) && mates.contains(&(1, 1)), "{:?}", mates);
+    assert_eq!(lines[0].score_string(), "#1");
+}
+
+#[test]
+fn test_fewer_legal_moves_than_requested() {
+    // 車封住 b 線，黑王只剩一格可走
+    let mut board = Board::new(5, 5);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::King, Color::White, (4, 4));
+    place(&mut board, PieceType::Rook, Color::White, (1, 4));
+    let legal = get_all_legal_moves(&board, Color::Black);
+    assert_eq!(legal.len(), 1);
+    let lines = choose_top_moves(&board, Color::Black, 3, 10);
+    assert_eq!(lines.len(), legal.len());
+}
+
+#[test]
+fn test_iterations_stream_every_line() {
+    let board = generate_standard_board(8, 8);
+    let mut searcher = Searcher::new(1 << 16);
+    searcher.options.multi_pv = 3;
+    let mut depths = Vec::new();
+    let result = searcher.search_with(&board, Color::Black, SearchLimits::depth(3), |info| {
+        assert_eq!(info.lines.len(), 3);
+        assert_eq!(info.pv, info.lines[0].pv);
+        depths.push(info.depth);
+    });
+    assert_eq!(depths, vec![1, 2, 3]);
+    assert_eq!(result.lines[0].pv_notation(8), result.pv_notation(8));
+}
@@ -0,0 +1,136 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use Chess_::engine::board::Board;
+use Chess_::engine::evaluator::piece_value;
+use Chess_::engine::generator::{generate_random_board_with, generate_standard_board};
+use Chess_::engine::minmax::Searcher;
+use Chess_::engine::movement::get_all_legal_moves;
+use Chess_::engine::moves::Move;
+use Chess_::engine::ordering::{mvv_lva, MoveOrdering};
+use Chess_::engine::piece::Piece;
+use Chess_::engine::types::{Color, PieceType};
+
+mod common;
+use common::place;
+
+/// 白兵、白騎士都能吃黑后，白后能吃黑兵
+fn capture_board() -> Board {
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::King, Color::White, (0, 7));
+    place(&mut board, PieceType::King, Color::Black, (0, 2));
+    place(&mut board, PieceType::Queen, Color::Black, (4, 4));
+    place(&mut board, PieceType::Pawn, Color::White, (3, 5));
+    place(&mut board, PieceType::Knight, Color::White, (2, 5));
+    place(&mut board, PieceType::Queen, Color::White, (6, 0));
+    place(&mut board, PieceType::Pawn, Color::Black, (6, 2));
+    board
+}
+
+fn find(moves: &[Move], from: (usize, usize), to: (usize, usize)) -> Move {
+    *moves.iter().find(|m| m.from == from && m.to == to).unwrap()
+}
+
+#[test]
+fn test_mvv_lva_prefers_valuable_victim_and_cheap_attacker() {
+    let board = capture_board();
+    let moves = get_all_legal_moves(&board, Color::White);
+    let pawn_takes_queen = find(&moves, (3, 5), (4, 4));
+    let knight_takes_queen = find(&moves, (2, 5), (4, 4));
+    let queen_takes_pawn = find(&moves, (6, 0), (6, 2));
+
+    assert!(mvv_lva(&board, &pawn_takes_queen, piece_value) > mvv_lva(&board, &knight_takes_queen, piece_value));
+    assert!(mvv_lva(&board, &knight_takes_queen, piece_value) > mvv_lva(&board, &queen_takes_pawn, piece_value));
+
+    let mut ordered = moves.clone();
+    MoveOrdering::new().order(&board, Color::White, &mut ordered, 0, None, piece_value);
+    assert_eq!(ordered[0], pawn_takes_queen);
+    assert_eq!(ordered[1], knight_takes_queen);
+    assert_eq!(ordered[2], queen_takes_pawn);
+}
+
+#[test]
+fn test_mvv_lva_uses_given_values() {
+    // 棋子價值由呼叫端提供：后若不值錢，吃后就排在吃兵後面
+    let board = capture_board();
+    let moves = get_all_legal_moves(&board, Color::White);
+    let knight_takes_queen = find(&moves, (2, 5), (4, 4));
+    let queen_takes_pawn = find(&moves, (6, 0), (6, 2));
+    let worthless_queen = |kind| if kind == PieceType::Queen { 0 } else { piece_value(kind) };
+    assert!(mvv_lva(&board, &queen_takes_pawn, worthless_queen) > mvv_lva(&board, &knight_takes_queen, worthless_queen));
+}
+
+#[test]
+fn test_hash_move_then_captures_then_killers() {
+    let board = capture_board();
+    let moves = get_all_legal_moves(&board, Color::White);
+    let hash_move = find(&moves, (0, 7), (1, 7));
+    let killer = find(&moves, (6, 0), (5, 0));
+
+    let mut ordering = MoveOrdering::new();
+    ordering.record_cutoff(Color::White, &killer, 2, 3);
+    assert_eq!(ordering.killers(2)[0], Some(killer));
+    assert!(ordering.history(Color::White, &killer) > 0);
+
+    let mut ordered = moves.clone();
+    ordering.order(&board, Color::White, &mut ordered, 2, Some(&hash_move), piece_value);
+    assert_eq!(ordered[0], hash_move);
+    assert!(ordered[1..4].iter().all(|m| m.is_capture()));
+    assert_eq!(ordered[4], killer);
+
+    // 殺手走法只屬於記錄的那一層，但歷史分數仍讓它排在其他不吃子走法前面
+    let mut other_ply = moves.clone();
+    ordering.order(&board, Color::White, &mut other_ply, 5, None, piece_value);
+    assert_eq!(other_ply[3], killer);
+}
+
+#[test]
+fn test_captures_never_become_killers() {
+    let board = capture_board();
+    let moves = get_all_legal_moves(&board, Color::White);
+    let capture = find(&moves, (3, 5), (4, 4));
+
+    let mut ordering = MoveOrdering::new();
+    ordering.record_cutoff(Color::White, &capture, 0, 4);
+    assert_eq!(ordering.killers(0), [None, None]);
+    assert_eq!(ordering.history(Color::White, &capture), 0);
+}
+
+/// 依相反順序放入同樣的棋子，棋子編號與雜湊表內的順序都會不同
+fn reversed(board: &Board) -> Board {
+    let mut pieces: Vec<Piece> = board.pieces.values().copied().collect();
+    pieces.sort_by_key(|p| std::cmp::Reverse(p.id));
+    let mut copy = Board::new(board.width, board.height);
+    for x in 0..board.width {
+        for y in 0..board.height {
+            if !board.is_tile_accessible((x, y)) {
+                copy.set_blocked((x, y));
+            }
+        }
+    }
+    copy.pawn_double_step = board.pawn_double_step;
+    for piece in pieces {
+        copy.add_piece(piece);
+    }
+    copy.castling = board.castling;
+    copy
+}
+
+#[test]
+fn test_search_is_deterministic() {
+    let mut rng = StdRng::seed_from_u64(12);
+    let mut boards = vec![generate_standard_board(8, 8)];
+    boards.extend((0..5).map(|_| generate_random_board_with(&mut rng, 6, 6, 4, 4, 3)));
+
+    for board in boards {
+        let copy = reversed(&board);
+        assert_eq!(copy.hash(), board.hash());
+
+        let mut a = Searcher::new(1 << 14);
+        let mut b = Searcher::new(1 << 14);
+        let first = a.choose_best_move(&board, Color::White, 3);
+        let second = b.choose_best_move(&copy, Color::White, 3);
+        assert_eq!(first.map(|m| (m.from, m.to, m.promotion)), second.map(|m| (m.from, m.to, m.promotion)));
+        assert_eq!(a.nodes(), b.nodes());
+    }
+}
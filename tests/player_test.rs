@@ -0,0 +1,93 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use Chess_::engine::game::{game_result, GameResult, GameState, Player, TurnState};
+use Chess_::engine::generator::{generate_random_board_with, generate_standard_board};
+use Chess_::engine::minmax::SearchLimits;
+use Chess_::engine::movement::get_all_legal_moves;
+use Chess_::engine::skill::SkillLevel;
+use Chess_::engine::types::Color;
+
+#[test]
+fn test_default_players() {
+    let mut game = GameState::new(generate_standard_board(8, 8));
+    assert_eq!(game.player(Color::White), Player::Human);
+    assert_eq!(game.player(Color::Black), Player::Engine(SkillLevel::Master));
+    assert_eq!(game.engine_to_move(), None);
+    assert!(game.try_ai_move(SearchLimits::depth(2)).is_none());
+
+    game.try_move((4, 6), (4, 4), None).unwrap();
+    assert_eq!(game.engine_to_move(), Some(SkillLevel::Master));
+}
+
+#[test]
+fn test_engine_plays_white() {
+    let mut game = GameState::new(generate_standard_board(8, 8));
+    game.set_player(Color::White, Player::Engine(SkillLevel::Intermediate));
+    assert!(game.white.is_engine());
+
+    let legal = get_all_legal_moves(&game.board, Color::White);
+    let result = game.try_ai_move(SearchLimits::depth(2)).unwrap();
+    assert!(legal.contains(&result.best_move.unwrap()));
+    assert_eq!(game.turn, TurnState::Black);
+
+    // 黑方交給玩家後，電腦不再代為走子
+    game.set_player(Color::Black, Player::Human);
+    assert!(game.try_ai_move(SearchLimits::depth(2)).is_none());
+    assert_eq!(game.turn, TurnState::Black);
+}
+
+#[test]
+fn test_human_vs_human_never_moves() {
+    let mut game = GameState::new(generate_standard_board(8, 8));
+    game.set_player(Color::Black, Player::Human);
+    game.try_move((4, 6), (4, 4), None).unwrap();
+    assert!(!game.black.is_engine());
+    assert!(game.try_ai_move(SearchLimits::depth(2)).is_none());
+}
+
+#[test]
+fn test_engine_vs_engine_autoplay() {
+    // 雙方都是電腦：輪流走子直到對局結束或達到步數上限，每一步都必須合法
+    let mut rng = StdRng::seed_from_u64(21);
+    for _ in 0..3 {
+        let mut game = GameState::new(generate_random_board_with(&mut rng, 6, 6, 4, 4, 2));
+        game.white = Player::Engine(SkillLevel::Novice);
+        game.black = Player::Engine(SkillLevel::Beginner);
+        for _ in 0..20 {
+            let side = game.turn.as_color();
+            let legal = get_all_legal_moves(&game.board, side);
+            match game.try_ai_move(SearchLimits::depth(2)) {
+                Some(result) => {
+                    assert!(legal.contains(&result.best_move.unwrap()));
+                    assert_eq!(game.turn.as_color(), side.opposite());
+                }
+                None => {
+                    assert!(legal.is_empty());
+                    assert!(matches!(
+                        game_result(&game.board, side),
+                        Some(GameResult::Checkmate { .. } | GameResult::Stalemate)
+                    ));
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_seeded_engine_games_replay() {
+    // 相同種子的電腦對局每一步都相同
+    let play = |seed: u64| {
+        let mut game = GameState::new(generate_standard_board(8, 8));
+        game.seed(seed);
+        game.white = Player::Engine(SkillLevel::Beginner);
+        game.black = Player::Engine(SkillLevel::Beginner);
+        (0..8)
+            .map_while(|_| game.try_ai_move(SearchLimits::depth(1)).and_then(|result| result.best_move))
+            .collect::<Vec<_>>()
+    };
+    let moves = play(11);
+    assert_eq!(moves.len(), 8);
+    assert_eq!(play(11), moves);
+}
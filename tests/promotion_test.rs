@@ -0,0 +1,63 @@
+use Chess_::engine::board::Board;
+use Chess_::engine::types::{Color, PieceType};
+use Chess_::engine::movement::{get_all_legal_moves, get_legal_move_list};
+use Chess_::engine::moves::Move;
+use Chess_::engine::game::GameState;
+use Chess_::engine::minmax::choose_best_move;
+
+mod common;
+use common::place;
+
+#[test]
+fn test_white_pawn_promotion_choices() {
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::Pawn, Color::White, (3, 1));
+
+    let pawn = board.get_piece_at((3, 1)).unwrap();
+    let moves = get_legal_move_list(&board, pawn);
+    assert_eq!(moves.len(), 4, "四種升變選擇");
+    for kind in [PieceType::Queen, PieceType::Rook, PieceType::Bishop, PieceType::Knight] {
+        assert!(moves.contains(&Move::with_promotion((3, 1), (3, 0), kind)));
+    }
+}
+
+#[test]
+fn test_black_pawn_promotes_on_last_rank_of_short_board() {
+    // 高度 5 的棋盤，黑兵在 y = 4 升變
+    let mut board = Board::new(6, 5);
+    place(&mut board, PieceType::Pawn, Color::Black, (2, 3));
+    place(&mut board, PieceType::Pawn, Color::Black, (4, 2));
+
+    let moves = get_all_legal_moves(&board, Color::Black);
+    assert!(moves.contains(&Move::with_promotion((2, 3), (2, 4), PieceType::Knight)));
+    assert!(moves.contains(&Move::new((4, 2), (4, 3))), "未到底線不升變");
+    assert!(!moves.contains(&Move::new((2, 3), (2, 4))), "到底線必須升變");
+}
+
+#[test]
+fn test_try_move_requires_promotion_choice() {
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::King, Color::White, (7, 7));
+    place(&mut board, PieceType::King, Color::Black, (0, 7));
+    place(&mut board, PieceType::Pawn, Color::White, (3, 1));
+    let mut game = GameState::new(board);
+
+    assert!(game.try_move((3, 1), (3, 0), None).is_err());
+    assert!(game.try_move((3, 1), (3, 0), Some(PieceType::King)).is_err());
+    assert!(game.try_move((3, 1), (3, 0), Some(PieceType::Knight)).is_ok());
+
+    let promoted = game.board.get_piece_at((3, 0)).unwrap();
+    assert_eq!(promoted.kind, PieceType::Knight);
+    assert_eq!(promoted.color, Color::White);
+}
+
+#[test]
+fn test_ai_promotes_to_queen() {
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::King, Color::White, (7, 0));
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::Pawn, Color::Black, (4, 6));
+
+    let best = choose_best_move(&board, Color::Black, 2).unwrap();
+    assert_eq!(best, Move::with_promotion((4, 6), (4, 7), PieceType::Queen));
+}
@@ -0,0 +1,116 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use Chess_::engine::board::Board;
+use Chess_::engine::generator::{generate_random_board_with, generate_standard_board};
+use Chess_::engine::minmax::{SearchLimits, SearchOptions, Searcher};
+use Chess_::engine::movement::get_all_legal_moves;
+use Chess_::engine::moves::MoveKind;
+use Chess_::engine::types::{Color, PieceType};
+
+mod common;
+use common::{place, seeded_mate_puzzles};
+
+/// 所有剪枝都關閉的選項（只剩 alpha-beta）
+fn no_pruning() -> SearchOptions {
+    SearchOptions {
+        null_move: false,
+        late_move_reductions: false,
+        pvs: false,
+        aspiration: false,
+        ..Default::default()
+    }
+}
+
+fn search(board: &Board, color: Color, depth: usize, options: SearchOptions) -> (i32, u64) {
+    let mut searcher = Searcher::new(1 << 16);
+    searcher.options = options;
+    let result = searcher.search(board, color, SearchLimits::depth(depth));
+    (result.score, result.nodes)
+}
+
+#[test]
+fn test_exact_pruning_keeps_the_score() {
+    // PVS 與渴望窗口只改變搜尋的窗口，分數必須與單純 alpha-beta 相同
+    // （靜態搜尋的 delta 剪枝與窗口有關，比較時關閉）
+    let mut rng = StdRng::seed_from_u64(18);
+    for _ in 0..10 {
+        let board = generate_random_board_with(&mut rng, 6, 6, 4, 4, 3);
+        for side in [Color::White, Color::Black] {
+            if get_all_legal_moves(&board, side).is_empty() {
+                continue;
+            }
+            let (plain, _) = search(&board, side, 4, SearchOptions { quiescence: false, ..no_pruning() });
+            let (exact, _) = search(&board, side, 4, SearchOptions { quiescence: false, ..SearchOptions::exact() });
+            assert_eq!(plain, exact, "{:?}", side);
+        }
+    }
+}
+
+#[test]
+fn test_each_technique_returns_legal_moves() {
+    let board = generate_standard_board(8, 8);
+    let none = no_pruning();
+    for options in [
+        SearchOptions { null_move: true, ..none },
+        SearchOptions { late_move_reductions: true, ..none },
+        SearchOptions { pvs: true, ..none },
+        SearchOptions { aspiration: true, ..none },
+        SearchOptions::default(),
+    ] {
+        let mut searcher = Searcher::new(1 << 16);
+        searcher.options = options;
+        let mv = searcher.choose_best_move(&board, Color::White, 4).unwrap();
+        assert!(get_all_legal_moves(&board, Color::White).contains(&mv), "{:?}", options);
+    }
+}
+
+#[test]
+fn test_pruning_searches_fewer_nodes() {
+    let board = generate_standard_board(8, 8);
+    let (_, plain) = search(&board, Color::White, 5, no_pruning());
+    let (_, pruned) = search(&board, Color::White, 5, SearchOptions::default());
+    assert!(pruned < plain, "剪枝後 {} 個節點，未剪枝 {} 個", pruned, plain);
+}
+
+#[test]
+fn test_mates_survive_pruning() {
+    for (board, _) in seeded_mate_puzzles(18, 3, (5, 5), (4, 1, 0), 2) {
+        let result = Searcher::new(1 << 16).search(&board, Color::White, SearchLimits::depth(5));
+        assert_eq!(result.mate_in(), Some(2));
+    }
+
+    // 黑方只剩王與被擋住的兵，只能走王：黑方不可使用空著，結果要與精確搜尋相同
+    let mut board = Board::new(5, 5);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::Pawn, Color::Black, (4, 1));
+    place(&mut board, PieceType::Pawn, Color::White, (4, 2));
+    place(&mut board, PieceType::King, Color::White, (2, 1));
+    place(&mut board, PieceType::Rook, Color::White, (3, 4));
+    let null_only = SearchOptions { late_move_reductions: false, ..SearchOptions::default() };
+    let (score, _) = search(&board, Color::White, 5, null_only);
+    let (exact, _) = search(&board, Color::White, 5, SearchOptions::exact());
+    assert_eq!(score, exact);
+}
+
+#[test]
+fn test_null_move_round_trip() {
+    let mut board = generate_standard_board(8, 8);
+    let double_step = get_all_legal_moves(&board, Color::White)
+        .into_iter()
+        .find(|mv| mv.from == (4, 6) && mv.to == (4, 4))
+        .unwrap();
+    assert_eq!(double_step.kind, MoveKind::DoubleStep);
+    board.make_move(&double_step);
+    assert_eq!(board.en_passant, Some((4, 5)));
+    let hash = board.hash();
+
+    // 空著讓過路兵機會消失，還原後要回來
+    let undo = board.make_null_move();
+    assert_eq!(board.en_passant, None);
+    assert_ne!(board.hash(), hash);
+    assert_eq!(board.hash(), board.compute_hash());
+    board.unmake_null_move(undo);
+    assert_eq!(board.en_passant, Some((4, 5)));
+    assert_eq!(board.hash(), hash);
+}
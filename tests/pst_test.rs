@@ -0,0 +1,132 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use Chess_::engine::board::Board;
+use Chess_::engine::eval_params::EvalParams;
+use Chess_::engine::evaluator::{evaluate_board, evaluate_board_verbose, piece_value};
+use Chess_::engine::generator::{generate_random_board_with, generate_standard_board};
+use Chess_::engine::pst::{game_phase, PhaseTables, PieceSquareTables, SquareTable, PHASE_TOTAL};
+use Chess_::engine::types::{Color, PieceType};
+
+mod common;
+use common::place;
+
+#[test]
+fn test_same_size_board_reads_table_directly() {
+    let tables = PieceSquareTables::default();
+    let knight = &tables.knight.middlegame;
+    assert_eq!(knight.sample((0, 0), Color::White, 8, 8), -1.0);
+    assert_eq!(knight.sample((3, 3), Color::White, 8, 8), 1.0);
+    assert_eq!(knight.sample((1, 4), Color::White, 8, 8), 0.0);
+
+    // 殘局的兵接近升變時加分
+    let pawn = &tables.pawn.endgame;
+    assert_eq!(pawn.sample((4, 1), Color::White, 8, 8), 1.0);
+    assert_eq!(pawn.sample((4, 6), Color::Black, 8, 8), 1.0, "黑方上下鏡射");
+    assert_eq!(pawn.sample((4, 5), Color::White, 8, 8), 0.0);
+}
+
+#[test]
+fn test_square_bonus_never_outweighs_material() {
+    // 預設表每格的加減分都小於一個輕子
+    let tables = PieceSquareTables::default();
+    for kind in [PieceType::Pawn, PieceType::Knight, PieceType::Bishop, PieceType::Rook, PieceType::Queen, PieceType::King] {
+        let phases = tables.tables(kind).unwrap();
+        for table in [&phases.middlegame, &phases.endgame] {
+            let largest = table.0.iter().flatten().map(|v| v.abs() * tables.weight).max().unwrap();
+            assert!(largest < piece_value(PieceType::Knight), "{:?} 的位置分數 {}", kind, largest);
+        }
+    }
+
+    // 即將升變的兵仍不如一個城堡
+    for size in [6, 8, 12] {
+        let mut board = Board::new(size, size);
+        place(&mut board, PieceType::King, Color::White, (0, size - 1));
+        place(&mut board, PieceType::King, Color::Black, (size - 1, size - 1));
+        place(&mut board, PieceType::Pawn, Color::White, (size / 2, 1));
+        place(&mut board, PieceType::Rook, Color::Black, (size - 1, 0));
+        assert!(evaluate_board(&board) < 0, "{}x{} 分數 {}", size, size, evaluate_board(&board));
+    }
+}
+
+#[test]
+fn test_table_resampled_to_board_size() {
+    let table = SquareTable(vec![vec![0, 4], vec![0, 4]]);
+    // 2x2 的表放到 4x4、3x3、10x10 的棋盤：兩端的格子取到表的兩端，中間依位置內插
+    assert_eq!(table.sample((0, 0), Color::White, 4, 4), 0.0);
+    assert_eq!(table.sample((3, 2), Color::White, 4, 4), 4.0);
+    assert_eq!(table.sample((1, 1), Color::White, 4, 4), 1.0);
+    assert_eq!(table.sample((1, 0), Color::White, 3, 3), 2.0);
+    let row: Vec<f32> = (0..10).map(|x| table.sample((x, 5), Color::White, 10, 10)).collect();
+    assert!(row.windows(2).all(|w| w[0] <= w[1]), "{:?}", row);
+    assert_eq!((row[0], row[9]), (0.0, 4.0));
+
+    assert_eq!(SquareTable::default().sample((2, 2), Color::White, 5, 5), 0.0);
+}
+
+#[test]
+fn test_tapered_between_middlegame_and_endgame() {
+    let tables = PhaseTables {
+        middlegame: SquareTable(vec![vec![2]]),
+        endgame: SquareTable(vec![vec![6]]),
+    };
+    assert_eq!(tables.sample((0, 0), Color::White, 8, 8, PHASE_TOTAL), 2.0);
+    assert_eq!(tables.sample((0, 0), Color::White, 8, 8, 0), 6.0);
+    assert_eq!(tables.sample((0, 0), Color::White, 8, 8, PHASE_TOTAL / 2), 4.0);
+
+    let only_middlegame = PhaseTables { middlegame: SquareTable(vec![vec![3]]), ..PhaseTables::default() };
+    assert_eq!(only_middlegame.sample((0, 0), Color::White, 8, 8, 0), 3.0, "省略殘局表時沿用中局表");
+
+    assert_eq!(game_phase(&generate_standard_board(8, 8)), PHASE_TOTAL);
+    assert_eq!(game_phase(&generate_standard_board(12, 12)), PHASE_TOTAL, "大棋盤以上限為準");
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::King, Color::White, (0, 0));
+    place(&mut board, PieceType::King, Color::Black, (7, 7));
+    place(&mut board, PieceType::Rook, Color::White, (3, 3));
+    assert_eq!(game_phase(&board), 2);
+}
+
+#[test]
+fn test_symmetric_positions_score_zero() {
+    for size in [6, 8, 10, 12] {
+        let board = generate_standard_board(size, size);
+        assert_eq!(evaluate_board(&board), 0, "{}x{} 的標準開局左右對稱", size, size);
+    }
+
+    // 把隨機局面上下翻轉並交換顏色，分數應該變號
+    let mut rng = StdRng::seed_from_u64(25);
+    for _ in 0..10 {
+        let board = generate_random_board_with(&mut rng, 9, 7, 4, 4, 4);
+        let mut mirrored = Board::new(board.width, board.height);
+        for piece in board.pieces.values() {
+            let pos = (piece.pos.0, board.height - 1 - piece.pos.1);
+            place(&mut mirrored, piece.kind, piece.color.opposite(), pos);
+        }
+        assert_eq!(evaluate_board(&mirrored), -evaluate_board(&board));
+    }
+}
+
+#[test]
+fn test_tables_from_params_file() {
+    let params = EvalParams::from_toml(
+        r#"
+        [positional]
+        weight = 2
+
+        [positional.knight]
+        middlegame = [[0, 3], [0, 3]]
+        "#,
+    )
+    .unwrap();
+    assert_eq!(params.positional.bishop, PieceSquareTables::default().bishop, "沒寫到的棋種沿用預設表");
+
+    let mut board = Board::new(10, 10);
+    place(&mut board, PieceType::King, Color::White, (0, 9));
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::Knight, Color::White, (9, 5));
+    let knight = params.positional.value(&board, PieceType::Knight, Color::White, (9, 5), game_phase(&board));
+    assert_eq!(knight, 6);
+
+    let default_knight = evaluate_board_verbose(&board).pieces.iter().find(|p| p.kind == PieceType::Knight).unwrap().positional;
+    assert_eq!(default_knight, -1, "預設表中邊線上的騎士扣分");
+}
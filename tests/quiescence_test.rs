@@ -0,0 +1,77 @@
+use Chess_::engine::board::Board;
+use Chess_::engine::minmax::{SearchOptions, Searcher};
+use Chess_::engine::types::{Color, PieceType};
+
+mod common;
+use common::place;
+
+fn search(board: &Board, color: Color, depth: usize, options: SearchOptions) -> (usize, usize) {
+    let mut searcher = Searcher::new(1 << 12);
+    searcher.options = options;
+    searcher.choose_best_move(board, color, depth).unwrap().to
+}
+
+fn without_quiescence() -> SearchOptions {
+    SearchOptions { quiescence: false, ..SearchOptions::default() }
+}
+
+/// 白后可以吃掉黑車，但黑車有兵保護，吃了之後后會被兵吃回
+fn defended_rook_for_white() -> Board {
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::King, Color::White, (7, 7));
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::Queen, Color::White, (3, 5));
+    place(&mut board, PieceType::Rook, Color::Black, (3, 3));
+    place(&mut board, PieceType::Pawn, Color::Black, (2, 2));
+    board
+}
+
+#[test]
+fn test_queen_does_not_take_defended_rook() {
+    let board = defended_rook_for_white();
+    // 舊的搜尋在深度 1 直接評分，看不到兵吃回后
+    assert_eq!(search(&board, Color::White, 1, without_quiescence()), (3, 3));
+    assert_ne!(search(&board, Color::White, 1, SearchOptions::default()), (3, 3));
+    assert_ne!(search(&board, Color::White, 3, SearchOptions::default()), (3, 3));
+}
+
+#[test]
+fn test_black_queen_does_not_take_defended_rook() {
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::King, Color::White, (7, 7));
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::Queen, Color::Black, (4, 2));
+    place(&mut board, PieceType::Rook, Color::White, (4, 4));
+    place(&mut board, PieceType::Pawn, Color::White, (5, 5));
+
+    assert_eq!(search(&board, Color::Black, 1, without_quiescence()), (4, 4));
+    assert_ne!(search(&board, Color::Black, 1, SearchOptions::default()), (4, 4));
+}
+
+#[test]
+fn test_undefended_rook_is_still_taken() {
+    // 沒有兵保護時，靜態搜尋不應該讓后放棄白吃的車
+    let mut board = defended_rook_for_white();
+    board.remove_piece_at((2, 2));
+    assert_eq!(search(&board, Color::White, 1, SearchOptions::default()), (3, 3));
+    assert_eq!(search(&board, Color::White, 2, SearchOptions::default()), (3, 3));
+}
+
+#[test]
+fn test_quiescence_depth_cap() {
+    // 上限為 0 時等同於不做靜態搜尋
+    let board = defended_rook_for_white();
+    let capped = SearchOptions { quiescence_depth: 0, ..SearchOptions::default() };
+    assert_eq!(search(&board, Color::White, 1, capped), (3, 3));
+
+    let one_ply = SearchOptions { quiescence_depth: 1, ..SearchOptions::default() };
+    assert_ne!(search(&board, Color::White, 1, one_ply), (3, 3));
+}
+
+#[test]
+fn test_quiescence_with_checks() {
+    let board = defended_rook_for_white();
+    let checks = SearchOptions { quiescence_checks: true, ..SearchOptions::default() };
+    assert_ne!(search(&board, Color::White, 1, checks), (3, 3));
+    assert_ne!(search(&board, Color::White, 2, checks), (3, 3));
+}
@@ -0,0 +1,101 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use Chess_::engine::board::Board;
+use Chess_::engine::game::{GameState, TurnState};
+use Chess_::engine::generator::generate_standard_board;
+use Chess_::engine::minmax::{SearchLimits, Searcher};
+use Chess_::engine::movement::get_all_legal_moves;
+use Chess_::engine::types::{Color, PieceType};
+
+mod common;
+use common::place;
+
+#[test]
+fn test_time_limit_is_respected() {
+    let board = generate_standard_board(8, 8);
+    let mut searcher = Searcher::new(1 << 16);
+    let start = Instant::now();
+    let mv = searcher.search(&board, Color::White, SearchLimits::time(Duration::from_millis(200))).best_move;
+    let elapsed = start.elapsed();
+
+    assert!(get_all_legal_moves(&board, Color::White).contains(&mv.unwrap()));
+    assert!(elapsed < Duration::from_millis(1000), "花了 {:?}", elapsed);
+}
+
+#[test]
+fn test_node_limit_is_respected() {
+    let board = generate_standard_board(8, 8);
+    let mut searcher = Searcher::new(1 << 16);
+    assert!(searcher.search(&board, Color::White, SearchLimits::nodes(5_000)).best_move.is_some());
+    assert!(searcher.nodes() >= 5_000);
+    assert!(searcher.nodes() < 5_100, "超出 {} 個節點", searcher.nodes());
+}
+
+#[test]
+fn test_first_iteration_always_completes() {
+    // 限制小到連第一輪都搜不完時，仍要回傳一步合法走法
+    let board = generate_standard_board(8, 8);
+    let mut searcher = Searcher::new(1 << 10);
+    let mv = searcher.search(&board, Color::White, SearchLimits::nodes(1)).best_move.unwrap();
+    assert!(get_all_legal_moves(&board, Color::White).contains(&mv));
+
+    let mv = searcher.search(&board, Color::Black, SearchLimits::time(Duration::ZERO)).best_move.unwrap();
+    assert!(get_all_legal_moves(&board, Color::Black).contains(&mv));
+}
+
+#[test]
+fn test_depth_limit_matches_fixed_depth_search() {
+    // 白后一步將死黑王
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::King, Color::White, (2, 1));
+    place(&mut board, PieceType::Queen, Color::White, (5, 5));
+
+    let limited = Searcher::new(1 << 12).search(&board, Color::White, SearchLimits::depth(3)).best_move.unwrap();
+    let fixed = Searcher::new(1 << 12).choose_best_move(&board, Color::White, 3).unwrap();
+    let timed = Searcher::new(1 << 12)
+        .search(&board, Color::White, SearchLimits::time(Duration::from_millis(100)))
+        .best_move
+        .unwrap();
+    for mv in [limited, fixed, timed] {
+        let mut work = board.clone();
+        work.make_move(&mv);
+        assert!(get_all_legal_moves(&work, Color::Black).is_empty(), "{:?} 不是將死", mv);
+    }
+}
+
+#[test]
+fn test_stop_token_cancels_search() {
+    // 沒有任何限制的搜尋，由另一個執行緒要求停止
+    let board = generate_standard_board(8, 8);
+    let mut searcher = Searcher::new(1 << 16);
+    let token = searcher.stop_token();
+    let start = Instant::now();
+    let result = thread::scope(|scope| {
+        scope.spawn(|| {
+            thread::sleep(Duration::from_millis(100));
+            token.stop();
+        });
+        searcher.search(&board, Color::White, SearchLimits::default().with_threads(2))
+    });
+
+    assert!(get_all_legal_moves(&board, Color::White).contains(&result.best_move.unwrap()));
+    assert!(start.elapsed() < Duration::from_millis(1000), "花了 {:?}", start.elapsed());
+
+    // 已停止的旗標仍會完成第一輪；重設後才恢復正常搜尋
+    assert_eq!(searcher.search(&board, Color::White, SearchLimits::depth(3)).depth, 1);
+    token.reset();
+    assert_eq!(searcher.search(&board, Color::White, SearchLimits::depth(3)).depth, 3);
+}
+
+#[test]
+fn test_ai_move_with_time_limit() {
+    let mut game = GameState::new(generate_standard_board(8, 8));
+    assert!(game.try_ai_move(SearchLimits::time(Duration::from_millis(50))).is_none(), "白方回合電腦不動");
+
+    game.try_move((4, 6), (4, 4), None).unwrap();
+    let mv = game.try_ai_move(SearchLimits::time(Duration::from_millis(50))).unwrap().best_move.unwrap();
+    assert_eq!(game.turn, TurnState::White);
+    assert!(game.board.get_piece_at(mv.to).is_some_and(|p| p.color == Color::Black));
+}
@@ -0,0 +1,141 @@
+use Chess_::engine::board::Board;
+use Chess_::engine::game::{game_result, GameResult};
+use Chess_::engine::generator::generate_standard_board;
+use Chess_::engine::minmax::{SearchLimits, Searcher, MATE_SCORE};
+use Chess_::engine::movement::get_all_legal_moves;
+use Chess_::engine::moves::{square_name, Move};
+use Chess_::engine::types::{Color, PieceType};
+
+mod common;
+use common::{place, seeded_mate_puzzles};
+
+/// 主要變例必須是從目前局面開始、雙方輪流的合法走法序列
+fn assert_legal_line(board: &Board, color: Color, pv: &[Move]) {
+    let mut work = board.clone();
+    let mut side = color;
+    for mv in pv {
+        assert!(get_all_legal_moves(&work, side).contains(mv), "{:?} 不合法", mv);
+        work.make_move(mv);
+        side = side.opposite();
+    }
+}
+
+#[test]
+fn test_result_has_pv_and_statistics() {
+    let board = generate_standard_board(8, 8);
+    let result = Searcher::new(1 << 16).search(&board, Color::White, SearchLimits::depth(4));
+
+    assert_eq!(result.depth, 4);
+    assert_eq!(result.pv.len(), 4);
+    assert_eq!(result.pv.first(), result.best_move.as_ref());
+    assert_legal_line(&board, Color::White, &result.pv);
+    assert!(result.nodes > 0);
+    assert!(result.nps > 0);
+    assert!(!result.elapsed.is_zero());
+}
+
+#[test]
+fn test_iterations_are_streamed() {
+    let board = generate_standard_board(8, 8);
+    let mut iterations = Vec::new();
+    let result = Searcher::new(1 << 16).search_with(&board, Color::Black, SearchLimits::depth(3), |info| {
+        iterations.push(info.clone());
+    });
+
+    assert_eq!(iterations.iter().map(|r| r.depth).collect::<Vec<_>>(), vec![1, 2, 3]);
+    for info in &iterations {
+        assert_eq!(info.pv.first(), info.best_move.as_ref());
+        assert_legal_line(&board, Color::Black, &info.pv);
+    }
+    assert!(iterations.windows(2).all(|w| w[0].nodes < w[1].nodes));
+
+    let last = iterations.last().unwrap();
+    assert_eq!((last.best_move, last.score, &last.pv), (result.best_move, result.score, &result.pv));
+}
+
+#[test]
+fn test_mate_score_and_pv() {
+    // 白后一步將死黑王
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::King, Color::White, (2, 1));
+    place(&mut board, PieceType::Queen, Color::White, (5, 5));
+
+    let result = Searcher::new(1 << 12).search(&board, Color::White, SearchLimits::depth(1));
+    assert_eq!(result.score, MATE_SCORE - 1);
+    assert_eq!(result.mate_in(), Some(1));
+    assert_eq!(result.pv.len(), 1);
+    assert_legal_line(&board, Color::White, &result.pv);
+}
+
+#[test]
+fn test_no_legal_moves() {
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::King, Color::White, (2, 1));
+    place(&mut board, PieceType::Queen, Color::White, (1, 1));
+
+    let mated = Searcher::new(1 << 10).search(&board, Color::Black, SearchLimits::depth(3));
+    assert_eq!(mated.best_move, None);
+    assert!(mated.pv.is_empty());
+    assert_eq!(mated.score, -MATE_SCORE);
+
+    // 把后移到 (1, 2) 變成逼和
+    board.remove_piece_at((1, 1));
+    place(&mut board, PieceType::Queen, Color::White, (1, 2));
+    let stalemate = Searcher::new(1 << 10).search(&board, Color::Black, SearchLimits::depth(3));
+    assert_eq!(stalemate.best_move, None);
+    assert_eq!(stalemate.score, 0);
+}
+
+#[test]
+fn test_move_notation() {
+    assert_eq!(Move::new((4, 6), (4, 4)).notation(8), "e2e4");
+    assert_eq!(Move::with_promotion((0, 1), (0, 0), PieceType::Knight).notation(8), "a7a8n");
+    assert_eq!(square_name((2, 0), 5), "c5");
+    assert_eq!(square_name((27, 3), 30), "[27]27");
+
+    let board = generate_standard_board(8, 8);
+    let result = Searcher::new(1 << 12).search(&board, Color::White, SearchLimits::depth(2));
+    assert_eq!(result.pv_notation(8).split(' ').count(), 2);
+}
+
+#[test]
+fn test_shortest_mate_and_mate_distance() {
+    // 黑王困在角落：白后有一步將死，也有較慢的將死
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::King, Color::White, (2, 1));
+    place(&mut board, PieceType::Queen, Color::White, (5, 5));
+
+    for depth in [3, 5] {
+        let result = Searcher::new(1 << 14).search(&board, Color::White, SearchLimits::depth(depth));
+        assert_eq!(result.mate_in(), Some(1), "深度 {} 仍要選最短的將死", depth);
+        assert_eq!(result.score_string(), "#1");
+    }
+
+    // 黑方觀點：一步後被將死
+    let mut after = board.clone();
+    after.remove_piece_at((5, 5));
+    place(&mut after, PieceType::Queen, Color::White, (5, 4));
+    let defending = Searcher::new(1 << 14).search(&after, Color::Black, SearchLimits::depth(4));
+    assert!(defending.mate_in().is_some_and(|n| n < 0), "{:?}", defending.score);
+}
+
+#[test]
+fn test_mate_in_two_puzzle() {
+    let (board, result) = seeded_mate_puzzles(14, 1, (5, 5), (4, 1, 0), 2).remove(0);
+    assert_eq!(result.mate_in(), Some(2));
+    assert_eq!(result.pv.len(), 3);
+    assert_legal_line(&board, Color::White, &result.pv);
+
+    let mut work = board.clone();
+    for mv in &result.pv {
+        work.make_move(mv);
+    }
+    assert_eq!(game_result(&work, Color::Black), Some(GameResult::Checkmate { winner: Color::White }));
+
+    // 不存在一步將死
+    let shallow = Searcher::new(1 << 12).search(&board, Color::White, SearchLimits::depth(1));
+    assert_ne!(shallow.mate_in(), Some(1));
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use Chess_::engine::game::{GameState, Player};
+use Chess_::engine::generator::generate_standard_board;
+use Chess_::engine::minmax::{PvLine, SearchLimits, Searcher, MATE_SCORE};
+use Chess_::engine::movement::get_all_legal_moves;
+use Chess_::engine::moves::Move;
+use Chess_::engine::skill::{pick_line, skill_search, SkillLevel};
+use Chess_::engine::types::Color;
+
+/// 分數由高到低的假候選變例，第一步以目標格區分
+fn lines(scores: &[i32]) -> Vec<PvLine> {
+    scores
+        .iter()
+        .enumerate()
+        .map(|(i, &score)| {
+            let mv = Move::new((0, 0), (i + 1, 0));
+            PvLine { best_move: mv, score, pv: vec![mv] }
+        })
+        .collect()
+}
+
+#[test]
+fn test_levels_get_stronger() {
+    let settings: Vec<_> = SkillLevel::ALL.iter().map(|level| level.settings()).collect();
+    for pair in settings.windows(2) {
+        let depth = |d: Option<usize>| d.unwrap_or(usize::MAX);
+        assert!(depth(pair[0].depth) <= depth(pair[1].depth));
+        assert!(pair[0].inaccuracy_chance >= pair[1].inaccuracy_chance);
+        assert!(pair[0].tolerance >= pair[1].tolerance);
+    }
+    let master = SkillLevel::Master.settings();
+    assert_eq!((master.candidates, master.tolerance, master.inaccuracy_chance), (1, 0, 0.0));
+    assert_eq!(SkillLevel::default(), SkillLevel::Master);
+
+    // 取較嚴格的限制
+    let beginner = SkillLevel::Beginner.settings();
+    let limits = beginner.apply(SearchLimits::depth(4));
+    assert_eq!((limits.depth, limits.nodes), (Some(1), beginner.nodes));
+    let limits = SkillLevel::Master.settings().apply(SearchLimits::nodes(10).with_threads(2));
+    assert_eq!((limits.depth, limits.nodes, limits.threads), (None, Some(10), 2));
+    assert!(SkillLevel::ALL.iter().all(|level| level.settings().apply(SearchLimits::default()).nodes.is_some()), "每個等級都要有上限");
+
+    // 以兵為單位的邊際依評估器的兵值換算
+    let scaled = beginner.scaled(100);
+    assert_eq!((scaled.tolerance, scaled.inaccuracy_margin), (100 * beginner.tolerance, 100 * beginner.inaccuracy_margin));
+    assert_eq!((scaled.depth, scaled.candidates), (beginner.depth, beginner.candidates));
+}
+
+#[test]
+fn test_pick_within_tolerance() {
+    let candidates = lines(&[5, 4, 3, 0]);
+    let mut settings = SkillLevel::Novice.settings();
+    settings.inaccuracy_chance = 0.0;
+    settings.tolerance = 1;
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut picked = Vec::new();
+    for _ in 0..100 {
+        let line = pick_line(&candidates, &settings, &mut rng).unwrap();
+        assert!(line.score >= 4);
+        picked.push(line.score);
+    }
+    assert!(picked.contains(&5) && picked.contains(&4), "應在接近最佳的走法中隨機挑選");
+
+    // 不容許誤差時一定選最佳走法
+    settings.tolerance = 0;
+    assert_eq!(pick_line(&candidates, &settings, &mut rng).unwrap().score, 5);
+    assert!(pick_line(&[], &settings, &mut rng).is_none());
+}
+
+#[test]
+fn test_inaccuracies_never_walk_into_mate() {
+    let mated = -(MATE_SCORE - 2);
+    let candidates = lines(&[1, 0, mated]);
+    let mut settings = SkillLevel::Beginner.settings();
+    settings.inaccuracy_chance = 1.0;
+    settings.inaccuracy_margin = i32::MAX / 2;
+
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut picked = Vec::new();
+    for _ in 0..100 {
+        picked.push(pick_line(&candidates, &settings, &mut rng).unwrap().score);
+    }
+    assert!(picked.contains(&0), "失誤時會選較差的走法");
+    assert!(!picked.contains(&mated));
+
+    // 每一步都會被將死時仍要選一步
+    let hopeless = lines(&[mated, mated - 1]);
+    assert!(pick_line(&hopeless, &settings, &mut rng).is_some());
+}
+
+#[test]
+fn test_every_level_plays_legal_moves() {
+    let board = generate_standard_board(8, 8);
+    let legal = get_all_legal_moves(&board, Color::White);
+    let mut rng = StdRng::seed_from_u64(3);
+    for level in SkillLevel::ALL {
+        let mut searcher = Searcher::new(1 << 14);
+        let result = skill_search(&mut searcher, &board, Color::White, level, SearchLimits::depth(3), &mut rng, |_| {});
+        let mv = result.best_move.unwrap();
+        assert!(legal.contains(&mv), "{:?}", level);
+        assert_eq!(result.pv.first(), Some(&mv));
+        assert!(result.lines.iter().any(|line| line.best_move == mv));
+        assert_eq!(result.lines.len(), level.settings().candidates);
+    }
+
+    // 大師等級與一般搜尋相同
+    let master = skill_search(
+        &mut Searcher::new(1 << 14),
+        &board,
+        Color::White,
+        SkillLevel::Master,
+        SearchLimits::depth(3),
+        &mut rng,
+        |_| {},
+    );
+    let plain = Searcher::new(1 << 14).search(&board, Color::White, SearchLimits::depth(3));
+    assert_eq!((master.best_move, master.score), (plain.best_move, plain.score));
+}
+
+#[test]
+fn test_game_uses_skill_level() {
+    let mut game = GameState::new(generate_standard_board(8, 8));
+    game.black = Player::Engine(SkillLevel::Beginner);
+    game.try_move((4, 6), (4, 4), None).unwrap();
+    let result = game.try_ai_move(SearchLimits::depth(4)).unwrap();
+    assert_eq!(result.depth, 1);
+    assert_eq!(result.lines.len(), SkillLevel::Beginner.settings().candidates);
+
+    // 同一盤棋的每一步都沿用同一張置換表
+    let table = game.searcher.tt.clone();
+    assert!(table.fill_rate() > 0.0);
+    game.try_move((3, 6), (3, 5), None).unwrap();
+    game.try_ai_move(SearchLimits::depth(4)).unwrap();
+    assert!(Arc::ptr_eq(&table, &game.searcher.tt));
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use Chess_::engine::board::Board;
+use Chess_::engine::generator::{generate_random_board_with, generate_standard_board};
+use Chess_::engine::minmax::{SearchLimits, Searcher};
+use Chess_::engine::movement::get_all_legal_moves;
+use Chess_::engine::tt::TranspositionTable;
+use Chess_::engine::types::{Color, PieceType};
+
+mod common;
+use common::place;
+
+#[test]
+fn test_legal_move_for_every_thread_count() {
+    let board = generate_standard_board(8, 8);
+    for threads in [0, 1, 2, 4] {
+        let result = Searcher::new(1 << 16).search(&board, Color::White, SearchLimits::depth(3).with_threads(threads));
+        assert_eq!(result.depth, 3);
+        assert!(get_all_legal_moves(&board, Color::White).contains(&result.best_move.unwrap()), "{} 執行緒", threads);
+    }
+}
+
+#[test]
+fn test_random_boards_with_threads() {
+    let mut rng = StdRng::seed_from_u64(16);
+    for _ in 0..5 {
+        let board = generate_random_board_with(&mut rng, 6, 6, 4, 4, 3);
+        let legal = get_all_legal_moves(&board, Color::Black);
+        let result = Searcher::new(1 << 14).search(&board, Color::Black, SearchLimits::depth(3).with_threads(3));
+        match result.best_move {
+            Some(mv) => assert!(legal.contains(&mv)),
+            None => assert!(legal.is_empty()),
+        }
+    }
+}
+
+#[test]
+fn test_mate_found_with_threads() {
+    // 白后一步將死黑王
+    let mut board = Board::new(6, 6);
+    place(&mut board, PieceType::King, Color::Black, (0, 0));
+    place(&mut board, PieceType::King, Color::White, (2, 1));
+    place(&mut board, PieceType::Queen, Color::White, (5, 5));
+
+    let result = Searcher::new(1 << 14).search(&board, Color::White, SearchLimits::depth(5).with_threads(4));
+    assert_eq!(result.mate_in(), Some(1));
+}
+
+#[test]
+fn test_time_limit_with_threads() {
+    let board = generate_standard_board(8, 8);
+    let start = Instant::now();
+    let limits = SearchLimits::time(Duration::from_millis(200)).with_threads(4);
+    let result = Searcher::new(1 << 16).search(&board, Color::White, limits);
+    let elapsed = start.elapsed();
+
+    assert!(result.best_move.is_some());
+    assert!(elapsed < Duration::from_millis(1000), "花了 {:?}", elapsed);
+}
+
+#[test]
+fn test_helpers_share_the_table() {
+    // 輔助執行緒的節點數要算進結果，且寫入同一張置換表
+    let board = generate_standard_board(8, 8);
+    let tt = Arc::new(TranspositionTable::new(1 << 16));
+    let mut searcher = Searcher::with_table(tt.clone());
+    let result = searcher.search(&board, Color::White, SearchLimits::depth(4).with_threads(4));
+
+    assert!(result.nodes >= searcher.nodes());
+    assert!(tt.stats().stores > 0);
+    assert!(tt.fill_rate() > 0.0);
+}
@@ -0,0 +1,121 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use Chess_::engine::board::Board;
+use Chess_::engine::evaluator::evaluate_board;
+use Chess_::engine::generator::{generate_random_board_with, generate_standard_board};
+use Chess_::engine::minmax::{SearchOptions, Searcher, MATE_SCORE};
+use Chess_::engine::movement::{generate_legal_moves, is_in_check};
+use Chess_::engine::moves::Move;
+use Chess_::engine::tt::{Bound, TranspositionTable};
+use Chess_::engine::types::Color;
+
+/// 不剪枝、不查表的 minimax（白方觀點），作為關閉靜態搜尋時置換表搜尋的對照
+fn plain_minimax(board: &mut Board, depth: usize, side: Color) -> i32 {
+    let moves = generate_legal_moves(board, side);
+    if moves.is_empty() {
+        if !is_in_check(board, side) {
+            return 0;
+        }
+        return if side == Color::White { -MATE_SCORE } else { MATE_SCORE };
+    }
+    if depth == 0 {
+        return evaluate_board(board);
+    }
+    let scores = moves.iter().map(|mv| {
+        let undo = board.make_move(mv);
+        let score = plain_minimax(board, depth - 1, side.opposite());
+        board.unmake_move(mv, undo);
+        score
+    });
+    if side == Color::White { scores.max().unwrap() } else { scores.min().unwrap() }
+}
+
+/// 走 `mv` 之後以 `depth - 1` 層對照搜尋的分數
+fn score_after(board: &Board, mv: &Move, side: Color, depth: usize) -> i32 {
+    let mut work = board.clone();
+    work.make_move(mv);
+    plain_minimax(&mut work, depth - 1, side.opposite())
+}
+
+#[test]
+fn test_table_size_and_replacement() {
+    assert_eq!(TranspositionTable::new(1000).capacity(), 512);
+    assert_eq!(TranspositionTable::new(0).capacity(), 1);
+    assert!(TranspositionTable::with_megabytes(1).capacity().is_power_of_two());
+
+    let tt = TranspositionTable::new(16);
+    assert!(tt.probe(42).is_none());
+    tt.store(42, 3, Bound::Lower, 7, None);
+    // 同一局面較淺的結果不覆蓋較深的結果
+    tt.store(42, 1, Bound::Exact, 1, None);
+    let entry = tt.probe(42).unwrap();
+    assert_eq!((entry.depth, entry.bound, entry.score), (3, Bound::Lower, 7));
+
+    // 同一槽位的不同局面直接取代，舊的鍵不再命中
+    tt.store(42 + 16, 0, Bound::Upper, -5, None);
+    assert!(tt.probe(42).is_none());
+    assert_eq!(tt.probe(42 + 16).unwrap().score, -5);
+
+    let stats = tt.stats();
+    assert_eq!((stats.probes, stats.hits, stats.overwrites), (4, 2, 1));
+    assert_eq!(stats.hit_rate(), 0.5);
+
+    tt.clear();
+    assert!(tt.probe(42 + 16).is_none());
+    assert_eq!(tt.stats().hits, 0);
+}
+
+#[test]
+fn test_search_matches_plain_minimax() {
+    // 置換表搜尋選出的走法，分數必須等於不剪枝 minimax 的最佳分數
+    let mut rng = StdRng::seed_from_u64(9);
+    for _ in 0..15 {
+        let board = generate_random_board_with(&mut rng, 5, 5, 3, 3, 2);
+        for side in [Color::White, Color::Black] {
+            let depth = 3;
+            let mut searcher = Searcher::new(1 << 12);
+            searcher.options = SearchOptions { quiescence: false, ..SearchOptions::exact() };
+            let Some(mv) = searcher.choose_best_move(&board, side, depth) else {
+                continue;
+            };
+            let mut work = board.clone();
+            let best = plain_minimax(&mut work, depth, side);
+            assert_eq!(score_after(&board, &mv, side, depth), best, "{:?} 的走法 {:?}", side, mv);
+        }
+    }
+}
+
+#[test]
+fn test_tiny_table_still_correct() {
+    // 只有一格的置換表會不斷發生覆蓋，但結果仍必須正確
+    let mut rng = StdRng::seed_from_u64(10);
+    for _ in 0..10 {
+        let board = generate_random_board_with(&mut rng, 5, 5, 3, 3, 2);
+        let mut searcher = Searcher::new(1);
+        searcher.options = SearchOptions { quiescence: false, ..SearchOptions::exact() };
+        let Some(mv) = searcher.choose_best_move(&board, Color::White, 3) else {
+            continue;
+        };
+        let mut work = board.clone();
+        let best = plain_minimax(&mut work, 3, Color::White);
+        assert_eq!(score_after(&board, &mv, Color::White, 3), best);
+    }
+}
+
+#[test]
+fn test_hit_rate_is_reported() {
+    let board = generate_standard_board(8, 8);
+    let mut searcher = Searcher::new(1 << 16);
+    assert!(searcher.choose_best_move(&board, Color::White, 4).is_some());
+    let first = searcher.tt_stats();
+    assert!(first.probes > 0);
+    assert!(first.hits > 0, "轉置局面應命中置換表");
+    assert!(searcher.tt.fill_rate() > 0.0);
+
+    // 同一個搜尋器再搜一次，上次的結果讓命中率上升
+    searcher.choose_best_move(&board, Color::White, 4);
+    let second = searcher.tt_stats();
+    let rate = (second.hits - first.hits) as f64 / (second.probes - first.probes) as f64;
+    assert!(rate > first.hit_rate());
+}
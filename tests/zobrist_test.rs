@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use Chess_::engine::board::Board;
+use Chess_::engine::game::GameState;
+use Chess_::engine::generator::{generate_random_board_with, generate_standard_board, generate_unique_boards};
+use Chess_::engine::movement::get_all_legal_moves;
+use Chess_::engine::types::{Color, PieceType};
+use Chess_::engine::zobrist::position_hash;
+
+mod common;
+use common::place;
+
+/// 每一步 make/unmake 後，增量雜湊都要等於重新計算的結果，且還原後回到原值
+fn assert_incremental(board: &Board, color: Color) {
+    let mut work = board.clone();
+    let before = work.hash();
+    assert_eq!(before, work.compute_hash());
+    for mv in get_all_legal_moves(board, color) {
+        let undo = work.make_move(&mv);
+        assert_eq!(work.hash(), work.compute_hash(), "{:?} 後雜湊不一致", mv);
+        assert_ne!(work.hash(), before);
+        work.unmake_move(&mv, undo);
+        assert_eq!(work.hash(), before);
+    }
+}
+
+#[test]
+fn test_incremental_hash_matches_full_recompute() {
+    assert_incremental(&generate_standard_board(8, 8), Color::White);
+    let mut rng = StdRng::seed_from_u64(8);
+    for _ in 0..10 {
+        let mut board = generate_random_board_with(&mut rng, 8, 8, 6, 6, 5);
+        board.pawn_double_step = true;
+        assert_incremental(&board, Color::White);
+        assert_incremental(&board, Color::Black);
+    }
+
+    // 升變、吃過路兵
+    let mut board = Board::new(6, 6);
+    board.pawn_double_step = true;
+    place(&mut board, PieceType::Pawn, Color::White, (1, 1));
+    place(&mut board, PieceType::Pawn, Color::White, (3, 2));
+    place(&mut board, PieceType::Pawn, Color::Black, (2, 2));
+    board.en_passant = Some((2, 1));
+    assert_incremental(&board, Color::White);
+}
+
+#[test]
+fn test_transposition_has_same_hash() {
+    let mut board = Board::new(8, 8);
+    place(&mut board, PieceType::King, Color::White, (4, 7));
+    place(&mut board, PieceType::Rook, Color::White, (0, 7));
+    place(&mut board, PieceType::Knight, Color::White, (1, 7));
+    place(&mut board, PieceType::King, Color::Black, (4, 0));
+    place(&mut board, PieceType::Rook, Color::Black, (7, 0));
+
+    let mut a = GameState::new(board.clone());
+    a.try_move((0, 7), (0, 5), None).unwrap();
+    a.try_move((7, 0), (7, 1), None).unwrap();
+    a.try_move((1, 7), (2, 5), None).unwrap();
+
+    let mut b = GameState::new(board);
+    b.try_move((1, 7), (2, 5), None).unwrap();
+    b.try_move((7, 0), (7, 1), None).unwrap();
+    b.try_move((0, 7), (0, 5), None).unwrap();
+
+    assert_eq!(a.hash(), b.hash());
+    assert_eq!(a.board.hash(), a.board.compute_hash());
+}
+
+#[test]
+fn test_hash_covers_side_dimensions_and_blocked_tiles() {
+    let board = Board::new(8, 8);
+    assert_ne!(position_hash(&board, Color::White), position_hash(&board, Color::Black));
+    assert_ne!(Board::new(8, 8).hash(), Board::new(8, 7).hash());
+    assert_ne!(Board::new(8, 8).hash(), Board::new(7, 8).hash());
+
+    let mut blocked = Board::new(8, 8);
+    blocked.set_blocked((3, 3));
+    assert_ne!(blocked.hash(), board.hash());
+    blocked.clear_tiles();
+    assert_eq!(blocked.hash(), board.hash());
+
+    let mut white = Board::new(8, 8);
+    place(&mut white, PieceType::Knight, Color::White, (2, 2));
+    let mut black = Board::new(8, 8);
+    place(&mut black, PieceType::Knight, Color::Black, (2, 2));
+    assert_ne!(white.hash(), black.hash());
+    white.clear_pieces();
+    assert_eq!(white.hash(), board.hash());
+}
+
+#[test]
+fn test_generate_unique_boards() {
+    let boards = generate_unique_boards(50, 5, 5, 2, 2, 2);
+    let hashes: HashSet<u64> = boards.iter().map(|b| b.hash()).collect();
+    assert_eq!(hashes.len(), 50);
+}